@@ -1,13 +1,190 @@
+use std::path::PathBuf;
+
+use crate::import::ImportedTheme;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
-    Tick,
-    Render,
     Resize(u16, u16),
-    Suspend,
-    Resume,
     Quit,
     Refresh,
     Error(String),
     Help,
-    Noop,
+    SelectNext,
+    SelectPrevious,
+    Apply,
+    ApplyTheme(PathBuf),
+    ToggleFavorite,
+    Filter,
+    SelectFirst,
+    SelectLast,
+    SelectLine(usize),
+    HalfPageDown,
+    HalfPageUp,
+    PageDown,
+    PageUp,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    SetMark,
+    JumpToMark,
+    Repeat(usize, Box<Action>),
+    SelectTab(usize),
+    NextTab,
+    PreviousTab,
+    CommandPalette,
+    RunCommand(String),
+    Random,
+    RestoreBackup,
+    ToggleLightDark,
+    OpenInEditor,
+    EditFile(PathBuf),
+    MigrateImport,
+    Adopt,
+    AdoptAs(String),
+    InstallOverlay,
+    EnableLiveReload,
+    Import,
+    ImportFile(PathBuf),
+    ImportThemes(Vec<ImportedTheme>),
+    Export,
+    ExportAs(String),
+    HooksRan(String, Vec<Result<String, String>>),
+    Confirm(Box<Action>),
+    DiscardChanges,
+}
+
+/// Actions that can be bound to keys, in the order they are documented
+pub const COMMANDS: &[Action] = &[
+    Action::Quit,
+    Action::Help,
+    Action::SelectNext,
+    Action::SelectPrevious,
+    Action::Apply,
+    Action::ToggleFavorite,
+    Action::Filter,
+    Action::SelectFirst,
+    Action::SelectLast,
+    Action::HalfPageDown,
+    Action::HalfPageUp,
+    Action::PageDown,
+    Action::PageUp,
+    Action::Search,
+    Action::NextMatch,
+    Action::PreviousMatch,
+    Action::SetMark,
+    Action::JumpToMark,
+    Action::CommandPalette,
+    Action::NextTab,
+    Action::PreviousTab,
+    Action::Random,
+    Action::ToggleLightDark,
+    Action::RestoreBackup,
+    Action::OpenInEditor,
+    Action::MigrateImport,
+    Action::Adopt,
+    Action::InstallOverlay,
+    Action::EnableLiveReload,
+    Action::Import,
+    Action::Export,
+];
+
+impl Action {
+    /// Name used to refer to the action in the keymap configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::SelectNext => "select-next",
+            Action::SelectPrevious => "select-previous",
+            Action::Apply => "apply",
+            Action::ToggleFavorite => "toggle-favorite",
+            Action::Filter => "filter",
+            Action::SelectFirst => "select-first",
+            Action::SelectLast => "select-last",
+            Action::HalfPageDown => "half-page-down",
+            Action::HalfPageUp => "half-page-up",
+            Action::PageDown => "page-down",
+            Action::PageUp => "page-up",
+            Action::Search => "search",
+            Action::NextMatch => "next-match",
+            Action::PreviousMatch => "previous-match",
+            Action::SetMark => "set-mark",
+            Action::JumpToMark => "jump-to-mark",
+            Action::CommandPalette => "command-palette",
+            Action::NextTab => "next-tab",
+            Action::PreviousTab => "previous-tab",
+            Action::Random => "random",
+            Action::ToggleLightDark => "toggle-light-dark",
+            Action::RestoreBackup => "restore-backup",
+            Action::OpenInEditor => "open-in-editor",
+            Action::MigrateImport => "migrate-import",
+            Action::Adopt => "adopt",
+            Action::InstallOverlay => "install-overlay",
+            Action::EnableLiveReload => "enable-live-reload",
+            Action::Import => "import",
+            Action::Export => "export",
+            _ => "",
+        }
+    }
+
+    /// Looks up a bindable action by its configuration name
+    pub fn from_name(name: &str) -> Option<Action> {
+        COMMANDS
+            .iter()
+            .find(|action| action.name() == name)
+            .cloned()
+    }
+
+    /// Short human readable description, used when listing key bindings
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Help => "Toggle this help",
+            Action::SelectNext => "Select next theme",
+            Action::SelectPrevious => "Select previous theme",
+            Action::Apply => "Apply selected theme",
+            Action::ToggleFavorite => "Toggle favorite",
+            Action::Filter => "Filter themes",
+            Action::SelectFirst => "Select first theme (or line of count)",
+            Action::SelectLast => "Select last theme (or line of count)",
+            Action::HalfPageDown => "Move half a page down",
+            Action::HalfPageUp => "Move half a page up",
+            Action::PageDown => "Move a page down",
+            Action::PageUp => "Move a page up",
+            Action::Search => "Search themes",
+            Action::NextMatch => "Jump to next search match",
+            Action::PreviousMatch => "Jump to previous search match",
+            Action::SetMark => "Mark selected theme (then a letter)",
+            Action::JumpToMark => "Jump to marked theme (then a letter)",
+            Action::CommandPalette => "Open the command palette",
+            Action::NextTab => "Switch to the next tab",
+            Action::PreviousTab => "Switch to the previous tab",
+            Action::Random => "Apply a random theme",
+            Action::ToggleLightDark => "Apply the light/dark variant of the theme",
+            Action::RestoreBackup => "Restore the configuration backup",
+            Action::OpenInEditor => "Open the selected theme in $EDITOR",
+            Action::MigrateImport => "Move the top-level import into [general]",
+            Action::Adopt => "Move the inline [colors] into a new theme (name optional)",
+            Action::InstallOverlay => "Apply themes through skins-active.toml only",
+            Action::EnableLiveReload => "Turn live_config_reload back on",
+            Action::Import => "Import the colour scheme of another terminal (path)",
+            Action::Export => "Export the selected theme to another terminal (format optional)",
+            _ => "",
+        }
+    }
+
+    /// Whether the action moves the selection, so a count repeats it
+    pub fn is_motion(&self) -> bool {
+        matches!(
+            self,
+            Action::SelectNext
+                | Action::SelectPrevious
+                | Action::HalfPageDown
+                | Action::HalfPageUp
+                | Action::PageDown
+                | Action::PageUp
+                | Action::NextMatch
+                | Action::PreviousMatch
+        )
+    }
 }
@@ -3,21 +3,12 @@ use std::{
     path::{Path, PathBuf},
 };
 
-#[derive(Debug)]
-pub enum SupportedPlatform {
-    Unix,
-    Windows,
-}
+use toml_edit::{table, value, Array, DocumentMut, Item, TableLike};
 
-pub fn detect_platform() -> Result<SupportedPlatform, String> {
-    return match env::consts::OS {
-        "linux" | "macos" => Ok(SupportedPlatform::Unix),
-        "windows" => Ok(SupportedPlatform::Windows),
-        other => Err(format!("Unsupported platform: {}", other)),
-    };
-}
+use crate::config;
+use crate::diff;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AlacrittyTheme {
     pub path: PathBuf,
     pub name: String,
@@ -31,67 +22,1040 @@ impl AlacrittyTheme {
     }
 }
 
-pub fn format_theme(theme_path: &PathBuf) -> &str {
+pub fn format_theme(theme_path: &Path) -> &str {
     let filename = theme_path.file_stem();
 
-    return match filename {
+    match filename {
         Some(filename) => filename.to_str().unwrap(),
         None => theme_path.to_str().unwrap(),
-    };
-}
-
-/// Gets the path to the Alacritty configuration file for the given platform
-pub fn get_config_file_path(platform: SupportedPlatform) -> Result<PathBuf, String> {
-    let mut possible_locations: Vec<String> = Vec::new();
-    let config_file_name = "alacritty.toml";
-
-    if let SupportedPlatform::Unix = platform {
-        // $XDG_CONFIG_HOME/alacritty/alacritty.toml
-        // $XDG_CONFIG_HOME/alacritty.toml
-        // $HOME/.config/alacritty/alacritty.toml
-        // $HOME/.alacritty.toml
-        let _ = env::var("XDG_CONFIG_HOME").inspect(|val| {
-            possible_locations.push(format!("{}/alacritty/{}", val, config_file_name));
-            possible_locations.push(format!("{}/{}", val, config_file_name));
-        });
+    }
+}
 
-        let _ = env::var("HOME").inspect(|val| {
-            possible_locations.push(format!("{}/.config/alacritty/{}", val, config_file_name));
-            possible_locations.push(format!("{}/{}", val, config_file_name));
-        });
-    } else if let SupportedPlatform::Windows = platform {
-        // %APPDATA%\alacritty\alacritty.toml
-        let _ = env::var("APPDATA").inspect(|val| {
-            possible_locations.push(format!("{}\\alacritty\\{}", val, config_file_name));
+/// Whether the configuration file is a legacy YAML one, read by Alacritty
+/// before 0.13
+pub fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "yml" || extension == "yaml")
+}
+
+// Gets all the themes available in the bundled themes directory and in the
+// user themes directory
+pub fn get_themes() -> Result<Vec<AlacrittyTheme>, String> {
+    let mut themes_dirs = vec![std::env::current_dir()
+        .map_err(|e| e.to_string())?
+        .join("themes")];
+    themes_dirs.extend(config::themes_dir());
+    themes_dirs.retain(|dir| dir.exists());
+
+    if themes_dirs.is_empty() {
+        return Err("Themes directory does not exist".to_string());
+    }
+
+    let mut themes = Vec::new();
+    for themes_dir in themes_dirs {
+        themes.extend(
+            fs::read_dir(&themes_dir)
+                .map_err(|e| format!("Unable to read {}: {}", themes_dir.display(), e))?
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|extension| extension == "toml")
+                })
+                .map(AlacrittyTheme::new),
+        );
+    }
+    themes.sort_by_key(|theme| theme.name.to_lowercase());
+
+    Ok(themes)
+}
+
+/// Keys Alacritty 0.14 moved from the top level into the `[general]` table
+pub const GENERAL_KEYS: [&str; 4] = [
+    "import",
+    "working_directory",
+    "live_config_reload",
+    "ipc_socket",
+];
+
+/// Where a configuration file keeps its list of imports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportLocation {
+    /// `import = [...]`, read by Alacritty before 0.14
+    TopLevel,
+    /// `[general] import = [...]`, read by Alacritty 0.14 and later
+    General,
+}
+
+/// Whether alacritty-skins can change a configuration file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Access {
+    Writable,
+    /// A symlink, as left by stow or chezmoi, to a writable file. Changes are
+    /// written to the target.
+    Symlink(PathBuf),
+    /// The reason the file cannot be changed
+    ReadOnly(String),
+}
+
+/// Where Nix, and home-manager with it, keeps the files it generates
+const NIX_STORE: &str = "/nix/store";
+
+/// File generated next to alacritty.toml in overlay mode, holding the import
+/// of the applied theme so alacritty.toml itself is never rewritten
+const OVERLAY_FILE_NAME: &str = "skins-active.toml";
+
+/// A change to a file, computed before it is written so it can be reviewed
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub path: PathBuf,
+    /// Contents of the file before the change, empty when it is created
+    pub before: String,
+    pub after: String,
+}
+
+impl Change {
+    pub fn new(path: &Path, after: String) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            before: fs::read_to_string(path).unwrap_or_default(),
+            after,
+        }
+    }
+
+    /// The change as a unified diff, empty when the contents are the same
+    pub fn diff(&self) -> String {
+        let new_name = self.path.display().to_string();
+        let old_name = match self.path.exists() {
+            true => new_name.as_str(),
+            false => "/dev/null",
+        };
+
+        diff::unified(&self.before, &self.after, old_name, &new_name)
+    }
+}
+
+/// An Alacritty configuration file, kept parsed so it can be edited in place
+/// without losing the user's comments and formatting
+#[derive(Debug, Clone)]
+pub struct AlacrittyConfig {
+    pub path: PathBuf,
+    document: DocumentMut,
+    /// Contents of the file when it was loaded, saved as the backup right
+    /// before the first change is written
+    original: String,
+    backed_up: bool,
+}
+
+impl AlacrittyConfig {
+    pub fn load(path: PathBuf) -> Result<Self, String> {
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+
+        let document = match contents.parse::<DocumentMut>() {
+            Ok(document) => document,
+            Err(error) => return Err(format!("Failed to parse configuration file: {}", error)),
+        };
+
+        Ok(Self {
+            path,
+            document,
+            original: contents,
+            backed_up: false,
+        })
+    }
+
+    /// Path of the backup taken before alacritty-skins first changes the file
+    pub fn backup_path(&self) -> PathBuf {
+        let mut backup = self.path.clone().into_os_string();
+        backup.push(".bak");
+        PathBuf::from(backup)
+    }
+
+    /// Detects which schema the file follows for its imports. Files without
+    /// any import use the current schema, unless they still have other keys
+    /// at the top level that Alacritty 0.14 moved into `[general]`.
+    pub fn import_location(&self) -> ImportLocation {
+        let general = self.document.get("general");
+        if general.is_some_and(|general| general.get("import").is_some()) {
+            return ImportLocation::General;
+        }
+
+        let legacy = GENERAL_KEYS
+            .iter()
+            .any(|key| self.document.contains_key(key));
+        match (legacy && general.is_none()) || self.document.contains_key("import") {
+            true => ImportLocation::TopLevel,
+            false => ImportLocation::General,
+        }
+    }
+
+    /// Files imported by the configuration, resolved and in the order
+    /// Alacritty loads them
+    pub fn imports(&self) -> Vec<PathBuf> {
+        let config_dir = self.path.parent().unwrap_or(Path::new("."));
+        imports(&self.document)
+            .iter()
+            .map(|import| resolve_import(import, config_dir))
+            .collect()
+    }
+
+    /// Detects whether the file is a symlink and whether it can be written,
+    /// following the symlink
+    pub fn access(&self) -> Access {
+        let is_symlink = fs::symlink_metadata(&self.path)
+            .is_ok_and(|metadata| metadata.file_type().is_symlink());
+        let target = match is_symlink {
+            true => fs::canonicalize(&self.path).unwrap_or(self.path.clone()),
+            false => self.path.clone(),
+        };
+        let linked = match is_symlink {
+            true => format!(" (linked from {})", self.path.display()),
+            false => String::new(),
+        };
+
+        if target.starts_with(NIX_STORE) {
+            return Access::ReadOnly(format!(
+                "{}{} is generated into the Nix store, by home-manager or NixOS, \
+                 and cannot be changed",
+                target.display(),
+                linked
+            ));
+        }
+        let read_only =
+            fs::metadata(&target).is_ok_and(|metadata| metadata.permissions().readonly());
+        if read_only || fs::OpenOptions::new().write(true).open(&target).is_err() {
+            return Access::ReadOnly(format!("{}{} is read-only", target.display(), linked));
+        }
+
+        match is_symlink {
+            true => Access::Symlink(target),
+            false => Access::Writable,
+        }
+    }
+
+    /// Refuses to go on when the file cannot be written, explaining how themes
+    /// can still be applied
+    fn check_writable(&self) -> Result<(), String> {
+        match self.access() {
+            Access::ReadOnly(reason) => Err(format!(
+                "{}. Change it where it is generated, or have it import {} once: \
+                 alacritty-skins then applies themes there",
+                reason,
+                contract_home(&self.overlay_path())
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn overlay_path(&self) -> PathBuf {
+        self.path.with_file_name(OVERLAY_FILE_NAME)
+    }
+
+    /// Whether the configuration already imports the overlay file
+    pub fn imports_overlay(&self) -> bool {
+        let overlay = self.overlay_path();
+        self.imports().contains(&overlay)
+    }
+
+    /// Themes imported by the configuration, through the overlay file too
+    pub fn theme_imports(&self) -> Vec<PathBuf> {
+        let mut imports = self.imports();
+        if self.imports_overlay() {
+            if let Ok(overlay) = Self::load(self.overlay_path()) {
+                imports.extend(overlay.imports());
+            }
+        }
+
+        imports
+    }
+
+    /// Adds the one-time import of the overlay file, last so the applied theme
+    /// overrides the other imports, and creates the overlay when missing.
+    /// Returns whether the import had to be added.
+    pub fn install_overlay(&mut self) -> Result<bool, String> {
+        let overlay = self.overlay_path();
+        if !overlay.exists() {
+            write(&self.empty_overlay())?;
+        }
+        if !self.add_overlay_import()? {
+            return Ok(false);
+        }
+        self.save()?;
+
+        Ok(true)
+    }
+
+    /// Changes `install_overlay` would make
+    pub fn install_overlay_changes(&self) -> Result<Vec<Change>, String> {
+        let mut changes = Vec::new();
+        if !self.overlay_path().exists() {
+            changes.push(self.empty_overlay());
+        }
+        let mut config = self.clone();
+        if config.add_overlay_import()? {
+            changes.push(config.pending_change());
+        }
+
+        Ok(changes)
+    }
+
+    fn empty_overlay(&self) -> Change {
+        Change::new(
+            &self.overlay_path(),
+            overlay_contents(None, self.import_location()),
+        )
+    }
+
+    /// Adds the import of the overlay file unless it is already there, without
+    /// saving. Returns whether it was added.
+    fn add_overlay_import(&mut self) -> Result<bool, String> {
+        if self.imports_overlay() {
+            return Ok(false);
+        }
+
+        self.check_writable()?;
+        let overlay = contract_home(&self.overlay_path());
+        self.imports_mut()?.push(overlay);
+
+        Ok(true)
+    }
+
+    /// Applies a theme by rewriting only the overlay file
+    pub fn set_overlay_theme(&self, theme: &AlacrittyTheme) -> Result<(), String> {
+        write(&self.overlay_change(theme))
+    }
+
+    /// Change of the overlay file applying the theme
+    pub fn overlay_change(&self, theme: &AlacrittyTheme) -> Change {
+        Change::new(
+            &self.overlay_path(),
+            overlay_contents(Some(theme), self.import_location()),
+        )
+    }
+
+    /// Whether themes go to the overlay file: in overlay mode, or as a fallback
+    /// when the configuration is read-only but imports the overlay anyway
+    pub fn uses_overlay(&self, overlay_mode: bool) -> bool {
+        let read_only = matches!(self.access(), Access::ReadOnly(_));
+        overlay_mode || (read_only && self.imports_overlay())
+    }
+
+    /// Applies a theme and saves the file, or rewrites the overlay file when
+    /// the overlay is used
+    pub fn apply(&mut self, theme: &AlacrittyTheme, overlay_mode: bool) -> Result<(), String> {
+        if self.uses_overlay(overlay_mode) {
+            return self.set_overlay_theme(theme);
+        }
+
+        self.set_theme(theme)?;
+        self.save()
+    }
+
+    /// Changes `apply` would make
+    pub fn apply_changes(
+        &self,
+        theme: &AlacrittyTheme,
+        overlay_mode: bool,
+    ) -> Result<Vec<Change>, String> {
+        if self.uses_overlay(overlay_mode) {
+            return Ok(vec![self.overlay_change(theme)]);
+        }
+
+        let mut config = self.clone();
+        config.set_theme(theme)?;
+
+        Ok(vec![config.pending_change()])
+    }
+
+    /// Change `migrate_to_general` and saving would make
+    pub fn migrate_change(&self) -> Result<Change, String> {
+        let mut config = self.clone();
+        config.migrate_to_general()?;
+
+        Ok(config.pending_change())
+    }
+
+    /// Change saving the edits made so far would make to the file
+    pub fn pending_change(&self) -> Change {
+        Change::new(&self.path, self.document.to_string())
+    }
+
+    /// Whether the file has a top-level `import`, deprecated by Alacritty 0.14
+    pub fn has_legacy_import(&self) -> bool {
+        self.document.contains_key("import")
+    }
+
+    /// The `[general]` table, created when missing
+    fn general_mut(&mut self) -> Result<&mut dyn TableLike, String> {
+        if !self.document.contains_key("general") {
+            self.document["general"] = table();
+        }
+
+        self.document["general"]
+            .as_table_like_mut()
+            .ok_or_else(|| "The `general` key of the configuration is not a table".to_string())
+    }
+
+    /// The list of imports in the place the file's schema expects it
+    fn imports_mut(&mut self) -> Result<&mut Array, String> {
+        let imports = match self.import_location() {
+            ImportLocation::TopLevel => self.document.as_table_mut().entry("import"),
+            ImportLocation::General => self.general_mut()?.entry("import"),
+        };
+
+        imports
+            .or_insert(value(Array::new()))
+            .as_array_mut()
+            .ok_or_else(|| "The `import` key of the configuration is not an array".to_string())
+    }
+
+    /// Moves the top-level keys Alacritty 0.14 deprecated, `import` included,
+    /// into `[general]`. Comments attached to the keys move along, and the
+    /// rest of the file is left untouched. Returns whether anything moved.
+    pub fn migrate_to_general(&mut self) -> Result<bool, String> {
+        let moved: Vec<_> = GENERAL_KEYS
+            .iter()
+            .filter_map(|key| self.document.as_table_mut().remove_entry(key))
+            .collect();
+        if moved.is_empty() {
+            return Ok(false);
+        }
+
+        let general = self.general_mut()?;
+        for (key, item) in moved {
+            match general.get_mut(key.get()) {
+                // Both locations import files: keep them all, the top-level
+                // ones first as Alacritty merges them in order
+                Some(existing) if key.get() == "import" => {
+                    let (Some(existing), Some(legacy)) = (existing.as_array_mut(), item.as_array())
+                    else {
+                        return Err("The `import` key of the configuration is not an array".into());
+                    };
+                    // Arrays on one line are laid out again, the items of
+                    // the others take the indentation of the item they push
+                    // down
+                    let multiline = existing
+                        .trailing()
+                        .as_str()
+                        .is_some_and(|trailing| trailing.contains('\n'))
+                        || existing.iter().any(|import| {
+                            import
+                                .decor()
+                                .prefix()
+                                .and_then(|prefix| prefix.as_str())
+                                .is_some_and(|prefix| prefix.contains('\n'))
+                        });
+                    for (index, import) in legacy.iter().enumerate() {
+                        if existing
+                            .iter()
+                            .any(|other| other.as_str() == import.as_str())
+                        {
+                            continue;
+                        }
+                        let indent = existing
+                            .get(index)
+                            .or(existing.iter().last())
+                            .and_then(|other| other.decor().prefix()?.as_str())
+                            .and_then(|prefix| prefix.rsplit('\n').next())
+                            .unwrap_or_default();
+                        let mut import = import.clone();
+                        import.decor_mut().clear();
+                        import.decor_mut().set_prefix(format!("\n{}", indent));
+                        existing.insert_formatted(index, import);
+                    }
+                    if !multiline {
+                        existing.fmt();
+                    }
+                }
+                // The value under `[general]` already wins in Alacritty
+                Some(_) => {}
+                None => {
+                    // The space after `=` is laid out again, the trailing
+                    // comment stays
+                    let mut item = item;
+                    if let Item::Value(value) = &mut item {
+                        let suffix = value.decor().suffix().cloned();
+                        value.decor_mut().clear();
+                        if let Some(suffix) = suffix {
+                            value.decor_mut().set_suffix(suffix);
+                        }
+                    }
+                    general.insert(key.get(), item);
+                    if let Some(mut key_mut) = general.key_mut(key.get()) {
+                        *key_mut.leaf_decor_mut() = key.leaf_decor().clone();
+                    }
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Whether the file turns `live_config_reload` off, so that Alacritty only
+    /// picks up an applied theme once restarted. The key under `[general]`
+    /// wins over the top-level one, as in Alacritty.
+    pub fn live_config_reload_disabled(&self) -> bool {
+        let general = self
+            .document
+            .get("general")
+            .and_then(|general| general.get("live_config_reload"));
+        let reload = general.or_else(|| self.document.get("live_config_reload"));
+
+        reload.and_then(Item::as_bool) == Some(false)
+    }
+
+    /// Turns `live_config_reload` back on where the file turns it off,
+    /// keeping its comments. Returns whether anything changed.
+    pub fn enable_live_config_reload(&mut self) -> bool {
+        if !self.live_config_reload_disabled() {
+            return false;
+        }
+
+        let in_general = self
+            .document
+            .get("general")
+            .and_then(|general| general.get("live_config_reload"))
+            .is_some();
+        let item = match in_general {
+            true => &mut self.document["general"]["live_config_reload"],
+            false => &mut self.document["live_config_reload"],
+        };
+        if let Some(reload) = item.as_value_mut() {
+            let decor = reload.decor().clone();
+            *reload = true.into();
+            *reload.decor_mut() = decor;
+        }
+
+        true
+    }
+
+    /// Change `enable_live_config_reload` and saving would make
+    pub fn live_config_reload_change(&self) -> Change {
+        let mut config = self.clone();
+        config.enable_live_config_reload();
+
+        config.pending_change()
+    }
+
+    /// Makes the configuration import the given theme. The last import
+    /// pointing to another theme of the same directory, or of the user themes
+    /// directory, is replaced, otherwise the theme is appended to the imports.
+    pub fn set_theme(&mut self, theme: &AlacrittyTheme) -> Result<(), String> {
+        let config_dir = self.path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let user_themes_dir = config::themes_dir();
+        let imports = self.imports_mut()?;
+
+        let themes_dir = theme.path.parent();
+        let theme_import = (0..imports.len()).rev().find(|&index| {
+            imports
+                .get(index)
+                .and_then(|import| import.as_str())
+                .is_some_and(|import| {
+                    let import_dir = resolve_import(import, &config_dir);
+                    let import_dir = import_dir.parent();
+                    import_dir == themes_dir || import_dir == user_themes_dir.as_deref()
+                })
         });
+
+        let import = contract_home(&theme.path);
+        match theme_import {
+            Some(index) => {
+                imports.replace(index, import);
+            }
+            None => imports.push(import),
+        }
+
+        Ok(())
     }
 
-    for config_file in possible_locations {
-        let path = Path::new(&config_file);
+    /// Moves the `[colors]` tables into a new theme of the user themes
+    /// directory, imported in their place. The import comes last so the theme
+    /// keeps overriding the other imports, as the inline colours did.
+    pub fn adopt(&mut self, name: Option<&str>) -> Result<AlacrittyTheme, String> {
+        let theme = self.take_colors(name)?;
+        if let Some(themes_dir) = theme.path.parent() {
+            fs::create_dir_all(themes_dir)
+                .map_err(|e| format!("Unable to create {}: {}", themes_dir.display(), e))?;
+        }
+        write(&theme)?;
+        self.save()?;
+
+        Ok(AlacrittyTheme::new(theme.path))
+    }
+
+    /// Changes `adopt` would make, the new theme first
+    pub fn adopt_changes(&self, name: Option<&str>) -> Result<Vec<Change>, String> {
+        let mut config = self.clone();
+        let theme = config.take_colors(name)?;
+
+        Ok(vec![theme, config.pending_change()])
+    }
+
+    /// Replaces the `[colors]` tables with the import of a new theme, without
+    /// saving. Returns the theme file to create.
+    fn take_colors(&mut self, name: Option<&str>) -> Result<Change, String> {
+        self.check_writable()?;
+        let themes_dir = config::themes_dir().ok_or("Could not find the user themes directory")?;
+        let path = match name {
+            Some(name) if name.is_empty() || name.contains(['/', '\\']) => {
+                return Err(format!("`{}` is not a valid theme name", name))
+            }
+            Some(name) => themes_dir.join(format!("{}.toml", name)),
+            // Named after the configuration file, as in `alacritty-colors`
+            None => {
+                let stem = format_theme(&self.path).trim_start_matches('.').to_string();
+                let mut path = themes_dir.join(format!("{}-colors.toml", stem));
+                for suffix in 2.. {
+                    if !path.exists() {
+                        break;
+                    }
+                    path = themes_dir.join(format!("{}-colors-{}.toml", stem, suffix));
+                }
+                path
+            }
+        };
         if path.exists() {
-            return Ok(path.to_path_buf());
+            return Err(format!("The theme {} already exists", path.display()));
         }
+
+        let Some((key, colors)) = self.document.as_table_mut().remove_entry("colors") else {
+            return Err(format!("{} has no [colors] to adopt", self.path.display()));
+        };
+        let mut theme = DocumentMut::new();
+        theme.insert(key.get(), colors);
+
+        self.imports_mut()?.push(contract_home(&path));
+
+        Ok(Change::new(
+            &path,
+            theme.to_string().trim_start().to_string(),
+        ))
     }
 
-    Err(String::from("Could not find configuration file"))
+    pub fn save(&mut self) -> Result<(), String> {
+        self.check_writable()?;
+        if !self.backed_up {
+            let backup = self.backup_path();
+            fs::write(&backup, &self.original)
+                .map_err(|e| format!("Unable to write {}: {}", backup.display(), e))?;
+            self.backed_up = true;
+        }
+
+        fs::write(&self.path, self.document.to_string())
+            .map_err(|e| format!("Unable to write {}: {}", self.path.display(), e))
+    }
+
+    /// Puts the backup back in place of the configuration file
+    pub fn restore_backup(&mut self) -> Result<(), String> {
+        let restored = self.load_backup()?;
+        write(&self.restore_change()?)?;
+        self.document = restored.document;
+
+        Ok(())
+    }
+
+    /// Change `restore_backup` would make
+    pub fn restore_change(&self) -> Result<Change, String> {
+        let restored = self.load_backup()?;
+
+        Ok(Change::new(&self.path, restored.original))
+    }
+
+    fn load_backup(&self) -> Result<Self, String> {
+        let backup = self.backup_path();
+        if !backup.exists() {
+            return Err(format!("There is no backup at {}", backup.display()));
+        }
+        self.check_writable()?;
+
+        Self::load(backup)
+    }
 }
 
-// Gets all the themes available in a given themes directory
-pub fn get_themes() -> Result<Vec<AlacrittyTheme>, String> {
-    let mut themes_dir = std::env::current_dir().map_err(|e| e.to_string())?;
-    themes_dir.push("themes");
+/// Writes a change to its file
+pub fn write(change: &Change) -> Result<(), String> {
+    fs::write(&change.path, &change.after)
+        .map_err(|e| format!("Unable to write {}: {}", change.path.display(), e))
+}
 
-    println!("{}", themes_dir.to_string_lossy());
-    if !themes_dir.exists() {
-        return Err("Themes directory does not exist".to_string());
+/// Contents of the overlay file importing the given theme, following the
+/// import schema of the configuration importing it
+fn overlay_contents(theme: Option<&AlacrittyTheme>, location: ImportLocation) -> String {
+    let mut document = DocumentMut::new();
+    let mut imports = Array::new();
+    imports.extend(theme.map(|theme| contract_home(&theme.path)));
+    match location {
+        ImportLocation::TopLevel => document["import"] = value(imports),
+        ImportLocation::General => {
+            document["general"] = table();
+            document["general"]["import"] = value(imports);
+        }
     }
 
-    let themes = fs::read_dir(themes_dir)
-        .map_err(|e| e.to_string())?
-        .map(|entry| entry.unwrap().path())
-        .filter(|path| path.extension().unwrap() == "toml")
-        .map(|path| AlacrittyTheme::new(path.to_path_buf()))
-        .collect();
+    format!(
+        "# Generated by alacritty-skins, changes are overwritten when applying a theme\n{}",
+        document
+    )
+}
+
+/// Writes a path under the home directory as `~/...`, so the configuration
+/// stays portable across machines
+pub fn contract_home(path: &Path) -> String {
+    let relative = env::var("HOME")
+        .ok()
+        .and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf));
+
+    match relative {
+        Some(relative) => format!("~/{}", relative.display()),
+        None => path.to_string_lossy().to_string(),
+    }
+}
+
+/// Imports listed by a configuration file as written, the deprecated
+/// top-level ones first
+pub fn imports(document: &DocumentMut) -> Vec<String> {
+    let top_level = document.get("import");
+    let general = document
+        .get("general")
+        .and_then(|general| general.get("import"));
+
+    [top_level, general]
+        .into_iter()
+        .flatten()
+        .filter_map(Item::as_array)
+        .flat_map(|imports| imports.iter().filter_map(|import| import.as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Resolves an import path the way Alacritty does: `~` and environment
+/// variables are expanded, and relative paths are relative to the directory
+/// of the importing file
+pub fn resolve_import(import: &str, config_dir: &Path) -> PathBuf {
+    config_dir.join(expand_home(&expand_env(import)))
+}
+
+/// Expands `$VAR` and `${VAR}`, leaving unset variables as they are
+fn expand_env(path: &str) -> String {
+    let mut expanded = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, end) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(close) => (&braced[..close], close + 2),
+                None => ("", 0),
+            },
+            None => {
+                let len = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..len], len)
+            }
+        };
 
-    return Ok(themes);
+        match env::var(name) {
+            Ok(value) if !name.is_empty() => expanded.push_str(&value),
+            _ => expanded.push_str(&rest[start..start + 1 + end]),
+        }
+        rest = &after[end..];
+    }
+    expanded.push_str(rest);
+
+    expanded
+}
+
+/// Expands a leading `~` the way Alacritty does for import paths
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var("HOME")) {
+        (Some(rest), Ok(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A configuration file with the given contents, in a directory of the
+    /// test
+    fn config(test: &str, contents: &str) -> AlacrittyConfig {
+        let dir = env::temp_dir().join(format!(
+            "alacritty-skins-alacritty-{}-{}",
+            test,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("alacritty.toml");
+        fs::write(&path, contents).unwrap();
+
+        AlacrittyConfig::load(path).unwrap()
+    }
+
+    /// The directory of a configuration, with a trailing separator
+    fn dir_of(config: &AlacrittyConfig) -> String {
+        format!("{}/", contract_home(config.path.parent().unwrap()))
+    }
+
+    #[test]
+    fn detects_the_import_location() {
+        let location = |test: &str, contents: &str| config(test, contents).import_location();
+
+        assert_eq!(location("location-empty", ""), ImportLocation::General);
+        assert_eq!(
+            location("location-top", "import = []\n[general]\n"),
+            ImportLocation::TopLevel
+        );
+        assert_eq!(
+            location("location-general", "[general]\nimport = []\n"),
+            ImportLocation::General
+        );
+        // Other deprecated keys tell an older file without imports
+        assert_eq!(
+            location("location-legacy", "live_config_reload = true\n"),
+            ImportLocation::TopLevel
+        );
+        assert_eq!(
+            location(
+                "location-mixed",
+                "live_config_reload = true\n[general]\nworking_directory = \"None\"\n"
+            ),
+            ImportLocation::General
+        );
+    }
+
+    #[test]
+    fn set_theme_replaces_the_import_of_the_same_directory() {
+        let mut config = config(
+            "set-theme-replace",
+            "[general]\n# Base first\nimport = [\"base.toml\", \"themes/old.toml\", \"local.toml\"] # mine\n",
+        );
+        let theme = AlacrittyTheme::new(config.path.with_file_name("themes").join("new.toml"));
+
+        config.set_theme(&theme).unwrap();
+
+        assert_eq!(
+            config.document.to_string(),
+            format!(
+                "[general]\n# Base first\nimport = [\"base.toml\", \"{}themes/new.toml\", \"local.toml\"] # mine\n",
+                dir_of(&config)
+            )
+        );
+    }
+
+    #[test]
+    fn set_theme_appends_to_the_imports_of_the_file_schema() {
+        let mut config = config("set-theme-append", "import = [\"base.toml\"]\n");
+        let theme = AlacrittyTheme::new(PathBuf::from("/elsewhere/Nord.toml"));
+
+        config.set_theme(&theme).unwrap();
+
+        assert_eq!(
+            config.document.to_string(),
+            "import = [\"base.toml\", \"/elsewhere/Nord.toml\"]\n"
+        );
+    }
+
+    #[test]
+    fn adopt_moves_the_colours_into_a_theme() {
+        let config = config(
+            "adopt",
+            "import = [\"base.toml\"]\n\n# Font\n[font]\nsize = 12\n\n\
+             # My colours\n[colors.primary]\nbackground = \"#000000\" # black\n",
+        );
+        let name = format!("adopt-test-{}", std::process::id());
+
+        let changes = config.adopt_changes(Some(&name)).unwrap();
+
+        let theme_path = config::themes_dir().unwrap().join(format!("{}.toml", name));
+        assert_eq!(changes[0].path, theme_path);
+        assert_eq!(
+            changes[0].after,
+            "# My colours\n[colors.primary]\nbackground = \"#000000\" # black\n"
+        );
+        assert_eq!(
+            changes[1].after,
+            format!(
+                "import = [\"base.toml\", \"{}\"]\n\n# Font\n[font]\nsize = 12\n",
+                contract_home(&theme_path)
+            )
+        );
+    }
+
+    #[test]
+    fn adopt_needs_colours_and_a_valid_name() {
+        let config = config("adopt-errors", "[font]\nsize = 12\n");
+
+        assert_eq!(
+            config.adopt_changes(Some("a/b")).unwrap_err(),
+            "`a/b` is not a valid theme name"
+        );
+        assert_eq!(
+            config
+                .adopt_changes(Some(&format!("adopt-none-{}", std::process::id())))
+                .unwrap_err(),
+            format!("{} has no [colors] to adopt", config.path.display())
+        );
+    }
+
+    #[test]
+    fn install_overlay_imports_it_last() {
+        let config = config("overlay", "[general]\nimport = [\"base.toml\"] # themes\n");
+
+        let changes = config.install_overlay_changes().unwrap();
+
+        assert_eq!(changes[0].path, config.overlay_path());
+        assert_eq!(
+            changes[0].after,
+            "# Generated by alacritty-skins, changes are overwritten when applying a theme\n\
+             [general]\nimport = []\n"
+        );
+        assert_eq!(
+            changes[1].after,
+            format!(
+                "[general]\nimport = [\"base.toml\", \"{}skins-active.toml\"] # themes\n",
+                dir_of(&config)
+            )
+        );
+    }
+
+    #[test]
+    fn install_overlay_is_done_once() {
+        let mut config = config("overlay-once", "import = [\"base.toml\"]\n");
+
+        assert!(config.install_overlay().unwrap());
+        assert!(config.imports_overlay());
+        assert!(!config.install_overlay().unwrap());
+        // The overlay follows the schema of the configuration
+        assert_eq!(
+            fs::read_to_string(config.overlay_path()).unwrap(),
+            "# Generated by alacritty-skins, changes are overwritten when applying a theme\n\
+             import = []\n"
+        );
+    }
+
+    #[test]
+    fn detects_read_only_files() {
+        let config = config("access-read-only", "");
+        assert_eq!(config.access(), Access::Writable);
+
+        let mut permissions = fs::metadata(&config.path).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&config.path, permissions).unwrap();
+
+        assert_eq!(
+            config.access(),
+            Access::ReadOnly(format!("{} is read-only", config.path.display()))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinks() {
+        let target = config("access-symlink", "");
+        let link = target.path.with_file_name("link.toml");
+        let _ = fs::remove_file(&link);
+        std::os::unix::fs::symlink(&target.path, &link).unwrap();
+
+        let config = AlacrittyConfig::load(link).unwrap();
+
+        assert_eq!(
+            config.access(),
+            Access::Symlink(fs::canonicalize(&target.path).unwrap())
+        );
+    }
+
+    #[test]
+    fn files_of_the_nix_store_are_read_only() {
+        let config = AlacrittyConfig {
+            path: PathBuf::from("/nix/store/abc-home-manager-files/alacritty.toml"),
+            document: DocumentMut::new(),
+            original: String::new(),
+            backed_up: false,
+        };
+
+        assert!(matches!(
+            config.access(),
+            Access::ReadOnly(reason) if reason.contains("generated into the Nix store")
+        ));
+    }
+
+    #[test]
+    fn enables_live_config_reload_keeping_comments() {
+        let mut config = config(
+            "live-reload",
+            "# Reload\nlive_config_reload = false # off\n",
+        );
+
+        assert!(config.enable_live_config_reload());
+        assert_eq!(
+            config.document.to_string(),
+            "# Reload\nlive_config_reload = true # off\n"
+        );
+        assert!(!config.enable_live_config_reload());
+    }
+
+    #[test]
+    fn enables_live_config_reload_where_it_wins() {
+        let mut config = config(
+            "live-reload-general",
+            "live_config_reload = true\n[general]\nlive_config_reload = false\n",
+        );
+
+        assert!(config.live_config_reload_disabled());
+        assert!(config.enable_live_config_reload());
+        assert_eq!(
+            config.document.to_string(),
+            "live_config_reload = true\n[general]\nlive_config_reload = true\n"
+        );
+    }
+
+    fn migrated(test: &str, contents: &str) -> String {
+        let mut config = config(test, contents);
+        assert!(config.migrate_to_general().unwrap());
+
+        config.document.to_string()
+    }
+
+    #[test]
+    fn migration_keeps_comments() {
+        let after = migrated(
+            "migrate-comments",
+            "# Themes\nimport = [\"a.toml\"] # trailing\n\n# Reload\nlive_config_reload = true\n\n\
+             [font]\nsize = 12\n",
+        );
+
+        assert_eq!(
+            after,
+            "\n[font]\nsize = 12\n\n[general]\n# Themes\nimport = [\"a.toml\"] # trailing\n\n\
+             # Reload\nlive_config_reload = true\n"
+        );
+    }
+
+    #[test]
+    fn migration_merges_imports_top_level_first() {
+        let after = migrated(
+            "migrate-merge",
+            "import = [\"a.toml\", \"c.toml\"]\n\n[general]\nimport = [\"b.toml\",\"c.toml\"]\n",
+        );
+
+        assert_eq!(
+            after,
+            "\n[general]\nimport = [\"a.toml\", \"b.toml\", \"c.toml\"]\n"
+        );
+    }
+
+    #[test]
+    fn migration_keeps_multiline_imports_laid_out() {
+        let after = migrated(
+            "migrate-multiline",
+            "import = [\"a.toml\"]\n\n[general]\nimport = [\n    # Base\n    \"b.toml\",\n]\n",
+        );
+
+        assert_eq!(
+            after,
+            "\n[general]\nimport = [\n    \"a.toml\",\n    # Base\n    \"b.toml\",\n]\n"
+        );
+    }
 }
@@ -0,0 +1,570 @@
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand};
+
+use crate::alacritty::{self, AlacrittyConfig, AlacrittyTheme, Change};
+use crate::config::Config;
+use crate::diff;
+use crate::export;
+use crate::fuzzy;
+use crate::gallery;
+use crate::hooks;
+use crate::import::{self, Imported, ImportedTheme, Mapping};
+use crate::locator;
+use crate::migrate;
+use crate::report::{self, Report};
+use crate::resolve::EffectiveColors;
+
+/// Preview and change the theme of the Alacritty terminal emulator. Without a
+/// command, opens the theme browser.
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Alacritty configuration file to use instead of the one found in the
+    /// usual locations
+    #[arg(long, global = true, env = "ALACRITTY_SKINS_CONFIG")]
+    pub config: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Apply a theme by name
+    Apply {
+        theme: String,
+        /// Print the changes instead of writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Put back the backup taken before alacritty.toml was first changed
+    Restore {
+        /// Print the changes instead of writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Convert a legacy alacritty.yml, and the YAML files it imports, to TOML.
+    /// The YAML files are kept as a backup.
+    Migrate {
+        /// Print the changes instead of writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print the colours Alacritty ends up using once every import is merged,
+    /// with the file each one comes from
+    Colors,
+    /// Move the [colors] of alacritty.toml into a new theme of the user themes
+    /// directory, and import that theme instead
+    Adopt {
+        /// Name of the new theme, derived from the configuration file if omitted
+        name: Option<String>,
+        /// Print the changes instead of writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import skins-active.toml from alacritty.toml once, and from then on
+    /// apply themes by rewriting only that file
+    InstallOverlay {
+        /// Print the changes instead of writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import the colour schemes of another terminal, such as an iTerm2
+    /// .itermcolors file, a Windows Terminal settings.json or a base16 YAML
+    /// scheme, into the user themes directory. A directory imports every
+    /// scheme it holds.
+    Import {
+        file: PathBuf,
+        /// Name of a scheme to import when the file holds several, can be
+        /// repeated
+        #[arg(long)]
+        scheme: Vec<String>,
+        /// Import every scheme of the file
+        #[arg(long, conflicts_with = "scheme")]
+        all: bool,
+        /// How base16 colours map onto the terminal palette, by default
+        /// base24 for base24 schemes and shell otherwise
+        #[arg(long, value_enum)]
+        mapping: Option<Mapping>,
+    },
+    /// Write the colours of a theme in the format of another terminal
+    Export {
+        theme: String,
+        #[arg(long, value_parser = PossibleValuesParser::new(export::names()))]
+        format: String,
+        /// File to write, instead of printing the colours
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Render every theme as an SVG mock-up, with an index.html to browse
+    /// them by light or dark and compare their contrast offline
+    Gallery {
+        /// Only render the themes whose name fuzzy matches the pattern
+        pattern: Option<String>,
+        /// Directory to write the gallery into
+        #[arg(long, short, default_value = "gallery")]
+        output: PathBuf,
+    },
+    /// Print a table of every theme with its family, variant, main colours,
+    /// lowest ANSI contrast and directory
+    Report {
+        #[arg(long, value_enum, default_value_t = report::Format::Markdown)]
+        format: report::Format,
+        /// File to write, instead of printing the report
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// List every location searched for the Alacritty configuration, in
+    /// order, and whether it exists
+    Paths,
+}
+
+impl Command {
+    /// Runs the command on the given Alacritty configuration file, or the one
+    /// found in the usual locations
+    pub fn run(self, config: Option<&Path>) -> Result<(), String> {
+        match self {
+            Command::Apply { theme, dry_run } => run_apply(config, &theme, dry_run),
+            Command::Restore { dry_run } => run_restore(config, dry_run),
+            Command::Migrate { dry_run } => run_migrate(config, dry_run),
+            Command::Colors => run_colors(config),
+            Command::Adopt { name, dry_run } => run_adopt(config, name.as_deref(), dry_run),
+            Command::InstallOverlay { dry_run } => run_install_overlay(config, dry_run),
+            Command::Import {
+                file,
+                scheme,
+                all,
+                mapping,
+            } => run_import(&file, &scheme, all, mapping),
+            Command::Export {
+                theme,
+                format,
+                output,
+            } => run_export(&theme, &format, output.as_deref()),
+            Command::Gallery { pattern, output } => run_gallery(pattern.as_deref(), &output),
+            Command::Report { format, output } => run_report(format, output.as_deref()),
+            Command::Paths => run_paths(config),
+        }
+    }
+}
+
+/// Finds the Alacritty configuration file, offering to create one when there
+/// is none, and refusing legacy YAML ones
+pub fn config_file(config: Option<&Path>) -> Result<PathBuf, String> {
+    let locator = locator::current()?;
+    let env = locator::environment();
+    let config_file = match config {
+        Some(path) if path.exists() => path.to_path_buf(),
+        Some(path) => offer_to_create(path)?,
+        None => match locator::find_config(locator.as_ref(), &env) {
+            Ok(path) => path,
+            Err(error) => match locator.preferred(&env) {
+                Some(path) => offer_to_create(&path)?,
+                None => {
+                    let unset = match locator.platform() {
+                        "windows" => "%APPDATA%",
+                        _ => "$HOME",
+                    };
+                    return Err(format!(
+                        "{}, and there is no user location to create one in ({} unset): \
+                         pass --config",
+                        error, unset
+                    ));
+                }
+            },
+        },
+    };
+    if alacritty::is_yaml(&config_file) {
+        return Err(format!(
+            "{} is a YAML configuration, which Alacritty no longer reads since 0.13. \
+             Run `alacritty-skins migrate` to convert it to TOML",
+            config_file.display()
+        ));
+    }
+
+    Ok(config_file)
+}
+
+/// Asks whether to create a minimal configuration at the given path, with an
+/// empty list of imports for the theme applied next
+fn offer_to_create(path: &Path) -> Result<PathBuf, String> {
+    let missing = format!(
+        "Could not find configuration file, create {} or pass --config",
+        path.display()
+    );
+    if !io::stdin().is_terminal() {
+        return Err(missing);
+    }
+
+    print!(
+        "No Alacritty configuration found. Create {} to import the theme? [Y/n] ",
+        path.display()
+    );
+    io::stdout().flush().map_err(|e| e.to_string())?;
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| e.to_string())?;
+    if !matches!(answer.trim(), "" | "y" | "Y" | "yes") {
+        return Err(missing);
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Unable to create {}: {}", dir.display(), e))?;
+    }
+    fs::write(path, "[general]\nimport = []\n")
+        .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+
+    Ok(path.to_path_buf())
+}
+
+/// Prints the diff of each change, coloured when printing to a terminal
+fn print_changes(changes: &[Change]) {
+    let diffs: Vec<String> = changes.iter().map(Change::diff).collect();
+    if diffs.iter().all(String::is_empty) {
+        println!("Nothing would change");
+        return;
+    }
+
+    for diff in diffs {
+        match io::stdout().is_terminal() {
+            true => print!("{}", diff::colorize(&diff)),
+            false => print!("{}", diff),
+        }
+    }
+}
+
+/// Finds a theme by name, ignoring case
+fn find_theme(name: &str) -> Result<AlacrittyTheme, String> {
+    alacritty::get_themes()?
+        .into_iter()
+        .find(|theme| theme.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown theme `{}`", name))
+}
+
+fn run_apply(config: Option<&Path>, name: &str, dry_run: bool) -> Result<(), String> {
+    let settings = Config::load()?;
+    let mut config = AlacrittyConfig::load(config_file(config)?)?;
+    let theme = find_theme(name)?;
+    let overlay_mode = settings.overlay();
+
+    if dry_run {
+        print_changes(&config.apply_changes(&theme, overlay_mode)?);
+        return Ok(());
+    }
+
+    config.apply(&theme, overlay_mode)?;
+    println!("Applied {}", theme.name);
+    let companions = settings.companions().unwrap_or_else(|error| {
+        eprintln!("warning: {}", error);
+        Vec::new()
+    });
+    for written in export::write_companions(&theme, &companions) {
+        match written {
+            Ok(path) => println!("Wrote {}", path.display()),
+            Err(error) => eprintln!("warning: {}", error),
+        }
+    }
+    let hooks = settings.hooks().unwrap_or_else(|error| {
+        eprintln!("warning: {}", error);
+        Vec::new()
+    });
+    for ran in hooks::run(&hooks, &theme) {
+        match ran {
+            Ok(message) => println!("{}", message),
+            Err(message) => eprintln!("warning: {}", message),
+        }
+    }
+    if config.live_config_reload_disabled() {
+        eprintln!(
+            "warning: {} turns live_config_reload off, restart Alacritty to see the theme",
+            config.path.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn run_restore(config: Option<&Path>, dry_run: bool) -> Result<(), String> {
+    let mut config = AlacrittyConfig::load(config_file(config)?)?;
+    if dry_run {
+        print_changes(&[config.restore_change()?]);
+        return Ok(());
+    }
+
+    config.restore_backup()?;
+    println!(
+        "Restored {} from {}",
+        config.path.display(),
+        config.backup_path().display()
+    );
+
+    Ok(())
+}
+
+fn run_migrate(config: Option<&Path>, dry_run: bool) -> Result<(), String> {
+    let config_file = match config {
+        Some(path) => path.to_path_buf(),
+        None => locator::find_config(locator::current()?.as_ref(), &locator::environment())?,
+    };
+    if !alacritty::is_yaml(&config_file) {
+        return Err(format!(
+            "{} is already a TOML configuration",
+            config_file.display()
+        ));
+    }
+
+    let migrations = migrate::migrate(&config_file)?;
+    for migration in &migrations {
+        print_changes(std::slice::from_ref(&migration.change));
+        for note in &migration.notes {
+            println!("note: {}", note);
+        }
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    for migration in &migrations {
+        alacritty::write(&migration.change)?;
+        println!(
+            "Wrote {}, kept {} as a backup",
+            migration.change.path.display(),
+            migration.source.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn run_colors(config: Option<&Path>) -> Result<(), String> {
+    let colors = EffectiveColors::resolve(&config_file(config)?)?;
+
+    for source in &colors.sources {
+        println!(
+            "{:<22} {}  {}",
+            source.name,
+            source.color.to_hex(),
+            source.file.display()
+        );
+    }
+    for warning in &colors.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    Ok(())
+}
+
+fn run_adopt(config: Option<&Path>, name: Option<&str>, dry_run: bool) -> Result<(), String> {
+    let mut config = AlacrittyConfig::load(config_file(config)?)?;
+    if dry_run {
+        print_changes(&config.adopt_changes(name)?);
+        return Ok(());
+    }
+
+    let theme = config.adopt(name)?;
+    println!(
+        "Moved the colours of {} into {}",
+        config.path.display(),
+        theme.path.display()
+    );
+
+    Ok(())
+}
+
+fn run_install_overlay(config: Option<&Path>, dry_run: bool) -> Result<(), String> {
+    let mut config = AlacrittyConfig::load(config_file(config)?)?;
+    if dry_run {
+        print_changes(&config.install_overlay_changes()?);
+        return Ok(());
+    }
+
+    let added = config.install_overlay()?;
+    Config::load()?.enable_overlay()?;
+    match added {
+        true => println!(
+            "{} now imports {}",
+            config.path.display(),
+            config.overlay_path().display()
+        ),
+        false => println!(
+            "{} already imports {}",
+            config.path.display(),
+            config.overlay_path().display()
+        ),
+    }
+
+    Ok(())
+}
+
+fn run_import(
+    file: &Path,
+    schemes: &[String],
+    all: bool,
+    mapping: Option<Mapping>,
+) -> Result<(), String> {
+    let Imported { themes, warnings } = import::read(file, mapping)?;
+    for warning in &warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let themes = if !schemes.is_empty() {
+        schemes
+            .iter()
+            .map(|name| {
+                themes
+                    .iter()
+                    .find(|theme| theme.name.eq_ignore_ascii_case(name))
+                    .cloned()
+                    .ok_or_else(|| format!("{} has no scheme `{}`", file.display(), name))
+            })
+            .collect::<Result<Vec<_>, String>>()?
+    } else if all || themes.len() <= 1 {
+        themes
+    } else {
+        pick_schemes(file, themes)?
+    };
+
+    // Keep going past the schemes that cannot be saved, as when importing a
+    // whole directory some of them may already be there
+    let mut imported = 0;
+    for theme in &themes {
+        match import::save(theme) {
+            Ok(saved) => {
+                println!("Imported {} into {}", theme.name, saved.path.display());
+                if !theme.unmapped.is_empty() {
+                    println!("note: left out {}", theme.unmapped.join(", "));
+                }
+                imported += 1;
+            }
+            Err(error) => eprintln!("warning: {}", error),
+        }
+    }
+    if imported == 0 {
+        return Err("Nothing imported".to_string());
+    }
+
+    Ok(())
+}
+
+/// Asks which of the schemes of a file to import
+fn pick_schemes(file: &Path, themes: Vec<ImportedTheme>) -> Result<Vec<ImportedTheme>, String> {
+    let names: Vec<&str> = themes.iter().map(|theme| theme.name.as_str()).collect();
+    if !io::stdin().is_terminal() {
+        return Err(format!(
+            "{} holds several schemes ({}), pick them with --scheme or pass --all",
+            file.display(),
+            names.join(", ")
+        ));
+    }
+
+    for (index, name) in names.iter().enumerate() {
+        println!("{:>3}  {}", index + 1, name);
+    }
+    print!("Import which schemes? (numbers separated by spaces, or `all`) ");
+    io::stdout().flush().map_err(|e| e.to_string())?;
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| e.to_string())?;
+    if answer.trim() == "all" {
+        return Ok(themes);
+    }
+
+    answer
+        .split_whitespace()
+        .map(|number| {
+            number
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| themes.get(number.checked_sub(1)?))
+                .cloned()
+                .ok_or_else(|| format!("`{}` is not one of the listed numbers", number))
+        })
+        .collect()
+}
+
+fn run_export(name: &str, format: &str, output: Option<&Path>) -> Result<(), String> {
+    let theme = find_theme(name)?;
+    let contents = export::export(&theme, export::exporter(format)?)?;
+
+    match output {
+        Some(path) => fs::write(path, contents)
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e)),
+        None => {
+            print!("{}", contents);
+            Ok(())
+        }
+    }
+}
+
+fn run_gallery(pattern: Option<&str>, output: &Path) -> Result<(), String> {
+    let mut themes = alacritty::get_themes()?;
+    if let Some(pattern) = pattern {
+        themes.retain(|theme| fuzzy::score(pattern, &theme.name).is_some());
+    }
+    if themes.is_empty() {
+        return Err("No theme matches the pattern".to_string());
+    }
+
+    let gallery = gallery::generate(&themes, output)?;
+    for warning in &gallery.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    println!(
+        "Rendered {} themes into {}",
+        gallery.count,
+        output.join("index.html").display()
+    );
+
+    Ok(())
+}
+
+fn run_report(format: report::Format, output: Option<&Path>) -> Result<(), String> {
+    let report = Report::new(&alacritty::get_themes()?);
+    for warning in &report.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let contents = report.render(format);
+    match output {
+        Some(path) => fs::write(path, contents)
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e)),
+        None => {
+            print!("{}", contents);
+            Ok(())
+        }
+    }
+}
+
+fn run_paths(config: Option<&Path>) -> Result<(), String> {
+    let locator = locator::current()?;
+    let env = locator::environment();
+    let candidates = locator.candidates(&env);
+    let used = match config {
+        Some(path) => Some(path.to_path_buf()),
+        None => candidates.iter().find(|path| path.exists()).cloned(),
+    };
+
+    println!(
+        "Alacritty configuration, in the {} search order:",
+        locator.platform()
+    );
+    if let Some(path) = config {
+        println!("  --config {}", path.display());
+    }
+    for path in &candidates {
+        let state = match (used.as_ref() == Some(path), path.exists()) {
+            (true, _) => "used",
+            (false, true) => "exists",
+            (false, false) => "missing",
+        };
+        println!("  {:<8}{}", state, path.display());
+    }
+
+    Ok(())
+}
@@ -3,8 +3,14 @@ use ratatui::{layout::Rect, Frame};
 
 use crate::action::Action;
 
-mod app;
-mod appmain;
+pub mod app;
+mod command_palette;
+mod export_dialog;
+mod help;
+mod import_picker;
+mod messages;
+mod preview;
+mod review;
 mod themes;
 
 /// `Component` is a trait that represents a visual and interactive element of
@@ -22,6 +28,7 @@ pub trait Component {
     /// # Returns
     ///
     /// * `Result<()>` - An Ok result or an error.
+    #[allow(unused_variables)]
     fn init(&mut self, area: Rect) -> Result<(), String> {
         Ok(())
     }
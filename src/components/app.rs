@@ -1,101 +1,926 @@
-use crate::tui;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::style::{Color, Style};
 use ratatui::symbols;
-use ratatui::widgets::{BorderType, List, ListState, Tabs};
-use ratatui::{
-    layout::Rect,
-    style::Stylize,
-    widgets::{Block, Borders},
-    Frame,
-};
-use std::io;
+use ratatui::widgets::{Paragraph, Tabs};
+use ratatui::{layout::Rect, style::Stylize, Frame};
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
 
+use super::command_palette::{self, CommandPalette};
+use super::export_dialog::ExportDialog;
+use super::help::Help;
+use super::import_picker::ImportPicker;
+use super::messages::Messages;
+use super::review::ReviewChanges;
+use super::themes::ThemesTab;
 use super::Component;
+use crate::action::Action;
+use crate::alacritty::{Access, AlacrittyConfig, AlacrittyTheme};
+use crate::config::Config;
+use crate::export;
+use crate::hooks;
+use crate::import::{self, Imported, ImportedTheme};
+use crate::keymap::{Key, Keymap, Resolution, Scope};
+use crate::resolve::{self, EffectiveColors};
+use crate::tui;
+
+/// How often the event loop checks on running hooks
+const HOOKS_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
-#[derive(Debug, Default)]
 pub struct App {
     exit: bool,
     selected_tab: Tab,
+    config: Config,
+    alacritty_config: AlacrittyConfig,
+    keymap: Keymap,
+    /// Keys typed so far of a multi-key chord
+    pending_keys: Vec<Key>,
+    /// Count typed before a motion, as in `10j`
+    count: Option<usize>,
+    status: Option<String>,
+    themes_tab: ThemesTab,
+    messages: Messages,
+    help: Option<Help>,
+    command_palette: Option<CommandPalette>,
+    review: Option<ReviewChanges>,
+    import_picker: Option<ImportPicker>,
+    export_dialog: Option<ExportDialog>,
+    /// Command lines run from the palette, most recent first
+    command_history: Vec<String>,
+    /// File to open in the user's editor once the terminal is released
+    editing: Option<PathBuf>,
+    /// Where the tab titles were last drawn
+    tabs_area: Rect,
+    /// Actions sent by the threads running hooks
+    action_tx: Sender<Action>,
+    action_rx: Receiver<Action>,
+    /// Hook threads that have not reported back yet
+    running_hooks: usize,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Tab {
     #[default]
     Themes,
+    Messages,
 }
 
-impl Component for App {
-    fn init(&mut self, area: Rect) -> Result<(), String> {
+impl Tab {
+    const ALL: [Tab; 2] = [Tab::Themes, Tab::Messages];
+
+    fn title(&self) -> &'static str {
+        match self {
+            Tab::Themes => "Themes",
+            Tab::Messages => "Messages",
+        }
+    }
+
+    fn scope(&self) -> Scope {
+        match self {
+            Tab::Themes => Scope::Themes,
+            Tab::Messages => Scope::Global,
+        }
+    }
+}
+
+impl App {
+    pub fn new(
+        config: Config,
+        keymap: Keymap,
+        alacritty_config: AlacrittyConfig,
+        themes: Vec<AlacrittyTheme>,
+    ) -> Self {
+        let filter_hint = keymap
+            .label_for(Scope::Themes, &Action::Filter)
+            .unwrap_or_default();
+        let themes_tab = ThemesTab::init(themes, config.favorites(), filter_hint);
+        let (action_tx, action_rx) = mpsc::channel();
+
+        let mut app = Self {
+            exit: false,
+            selected_tab: Tab::default(),
+            config,
+            alacritty_config,
+            keymap,
+            pending_keys: Vec::new(),
+            count: None,
+            status: None,
+            themes_tab,
+            messages: Messages::default(),
+            help: None,
+            command_palette: None,
+            review: None,
+            import_picker: None,
+            export_dialog: None,
+            command_history: Vec::new(),
+            editing: None,
+            tabs_area: Rect::default(),
+            action_tx,
+            action_rx,
+            running_hooks: 0,
+        };
+
+        if app.alacritty_config.has_legacy_import() {
+            app.notify(format!(
+                "{} uses the top-level `import` deprecated by Alacritty 0.14, {} to move it under [general]",
+                app.alacritty_config.path.display(),
+                app.how_to_run(&Action::MigrateImport)
+            ));
+        }
+        match app.alacritty_config.access() {
+            Access::Writable => {}
+            Access::Symlink(target) => app.messages.info(&format!(
+                "{} is a symlink, changes are written to {}",
+                app.alacritty_config.path.display(),
+                target.display()
+            )),
+            Access::ReadOnly(reason) if app.alacritty_config.imports_overlay() => app.notify(
+                format!("{}, themes are applied to the overlay it imports", reason),
+            ),
+            Access::ReadOnly(reason) => app.notify(format!(
+                "{}, themes cannot be applied until it imports {}",
+                reason,
+                app.alacritty_config.overlay_path().display()
+            )),
+        }
+        if app.config.overlay() && !app.alacritty_config.imports_overlay() {
+            app.notify(format!(
+                "{} does not import {} yet, {} to add it",
+                app.alacritty_config.path.display(),
+                app.alacritty_config.overlay_path().display(),
+                app.how_to_run(&Action::InstallOverlay)
+            ));
+        }
+        if app.alacritty_config.live_config_reload_disabled() {
+            app.messages.info(&format!(
+                "{} turns live_config_reload off, Alacritty only shows applied themes once restarted",
+                app.alacritty_config.path.display()
+            ));
+        }
+        app.check_applied_theme();
+
+        app
+    }
+
+    /// Warning kept in the status bar while the configuration turns
+    /// `live_config_reload` off
+    fn live_reload_warning(&self) -> Option<String> {
+        if !self.alacritty_config.live_config_reload_disabled() {
+            return None;
+        }
+
+        let fix = match self
+            .keymap
+            .label_for(Scope::Global, &Action::EnableLiveReload)
+        {
+            Some(key) => format!("press {}", key),
+            None => "run `enable-live-reload`".to_string(),
+        };
+        Some(format!(
+            "⚠ live_config_reload is off, {} to turn it on",
+            fix
+        ))
+    }
+
+    /// Warns about imports Alacritty cannot load, and about the theme found in
+    /// the imports when other files override its colours
+    fn check_applied_theme(&mut self) {
+        let colors = match EffectiveColors::resolve(&self.alacritty_config.path) {
+            Ok(colors) => colors,
+            Err(error) => return self.messages.error(&error),
+        };
+        for warning in &colors.warnings {
+            self.messages.error(warning);
+        }
+
+        let imports = self.alacritty_config.theme_imports();
+        let applied = self
+            .themes_tab
+            .themes()
+            .iter()
+            .rev()
+            .find(|theme| imports.contains(&theme.path))
+            .cloned();
+        if let Some(theme) = applied {
+            if let Some(shadowing) = Self::shadowing(&colors, &theme.path) {
+                self.notify(format!("{} is shadowed: {}", theme.name, shadowing));
+            }
+        }
+    }
+
+    /// Describes the colours of the theme other files override, if any
+    fn shadowing(colors: &EffectiveColors, theme: &Path) -> Option<String> {
+        let shadowing = colors.shadowing(theme).ok()?;
+        (!shadowing.is_empty()).then(|| resolve::describe_shadowing(&shadowing))
+    }
+
+    pub fn run(&mut self, terminal: &mut tui::Tui) -> Result<(), String> {
+        let area = terminal.size().map_err(|e| e.to_string())?;
+        self.init(area)?;
+
+        while !self.exit {
+            let mut result = Ok(());
+            terminal
+                .draw(|frame| result = self.draw(frame, frame.size()))
+                .map_err(|e| e.to_string())?;
+            result?;
+
+            // Wait for input, waking up now and then while hooks run to
+            // report them once they are done
+            let event = match self.running_hooks {
+                0 => Some(event::read().map_err(|e| e.to_string())?),
+                _ => match event::poll(HOOKS_POLL_INTERVAL).map_err(|e| e.to_string())? {
+                    true => Some(event::read().map_err(|e| e.to_string())?),
+                    false => None,
+                },
+            };
+            let mut action = self.handle_events(event)?;
+            while let Some(next) = action {
+                action = self.update(next)?;
+            }
+            while let Ok(sent) = self.action_rx.try_recv() {
+                let mut action = Some(sent);
+                while let Some(next) = action {
+                    action = self.update(next)?;
+                }
+            }
+
+            if let Some(path) = self.editing.take() {
+                tui::restore().map_err(|e| e.to_string())?;
+                let result = open_in_editor(&path);
+                *terminal = tui::init().map_err(|e| e.to_string())?;
+                terminal.clear().map_err(|e| e.to_string())?;
+
+                match result {
+                    Ok(()) => self.update(Action::Refresh)?,
+                    Err(error) => self.update(Action::Error(error))?,
+                };
+            }
+        }
+
         Ok(())
     }
 
-    fn handle_events(
-        &mut self,
-        event: Option<Event>,
-    ) -> Result<Option<crate::action::Action>, String> {
-        let r = match event {
-            Some(Event::Key(key_event)) => self.handle_key_events(key_event)?,
-            Some(Event::Mouse(mouse_event)) => self.handle_mouse_events(mouse_event)?,
-            _ => None,
+    /// Tells how to run a command: its key when it is bound to one, and its
+    /// name in the command palette
+    fn how_to_run(&self, action: &Action) -> String {
+        let palette = self
+            .keymap
+            .label_for(Scope::Global, &Action::CommandPalette)
+            .unwrap_or_else(|| ":".to_string());
+        let key = self
+            .keymap
+            .label_for(Scope::Global, action)
+            .or_else(|| self.keymap.label_for(Scope::Themes, action));
+
+        match key {
+            Some(key) => format!(
+                "press {} (or `{}` in the command palette, {})",
+                key,
+                action.name(),
+                palette
+            ),
+            None => format!(
+                "run `{}` from the command palette ({})",
+                action.name(),
+                palette
+            ),
+        }
+    }
+
+    /// Shows a message in the status bar and keeps it in the message log
+    fn notify(&mut self, message: String) {
+        self.messages.info(&message);
+        self.status = Some(message);
+    }
+
+    fn apply_theme(&mut self, path: &Path) -> Result<(), String> {
+        let theme = AlacrittyTheme::new(path.to_path_buf());
+        self.alacritty_config.apply(&theme, self.config.overlay())?;
+
+        let colors = EffectiveColors::resolve(&self.alacritty_config.path)?;
+        match Self::shadowing(&colors, &theme.path) {
+            Some(shadowing) => self.notify(format!(
+                "Applied {}, but it is shadowed: {}",
+                theme.name, shadowing
+            )),
+            None => self.notify(format!("Applied {}", theme.name)),
+        }
+        self.write_companions(&theme);
+        self.run_hooks(&theme);
+
+        Ok(())
+    }
+
+    /// Writes the companion files of the applied theme, logging the ones that
+    /// fail without undoing the apply
+    fn write_companions(&mut self, theme: &AlacrittyTheme) {
+        let companions = match self.config.companions() {
+            Ok(companions) => companions,
+            Err(error) => return self.messages.error(&error),
         };
+        if companions.is_empty() {
+            return;
+        }
 
-        Ok(r)
+        for written in export::write_companions(theme, &companions) {
+            match written {
+                Ok(path) => self.messages.info(&format!("Wrote {}", path.display())),
+                Err(error) => {
+                    self.messages.error(&error);
+                    self.status = Some(format!("Applied {}, but {}", theme.name, error));
+                }
+            }
+        }
     }
 
-    fn handle_key_events(
-        &mut self,
-        key: KeyEvent,
-    ) -> Result<Option<crate::action::Action>, String> {
-        Ok(None)
+    /// Runs the post-apply hooks on a thread of their own, so a slow hook
+    /// does not freeze the interface. Their results come back as
+    /// `Action::HooksRan`.
+    fn run_hooks(&mut self, theme: &AlacrittyTheme) {
+        let hooks = match self.config.hooks() {
+            Ok(hooks) => hooks,
+            Err(error) => return self.messages.error(&error),
+        };
+        if hooks.is_empty() {
+            return;
+        }
+
+        let theme = theme.clone();
+        let action_tx = self.action_tx.clone();
+        self.running_hooks += 1;
+        thread::spawn(move || {
+            let results = hooks::run(&hooks, &theme);
+            let _ = action_tx.send(Action::HooksRan(theme.name, results));
+        });
     }
 
-    fn handle_mouse_events(
-        &mut self,
-        mouse: event::MouseEvent,
-    ) -> Result<Option<crate::action::Action>, String> {
-        Ok(None)
+    /// Logs what the hooks printed and reports them in the status bar,
+    /// unless it shows a warning about the apply. A failing hook is reported
+    /// but the theme stays applied.
+    fn hooks_ran(&mut self, theme: &str, results: Vec<Result<String, String>>) {
+        self.running_hooks = self.running_hooks.saturating_sub(1);
+
+        let mut failure = None;
+        for ran in &results {
+            match ran {
+                Ok(message) => self.messages.info(message),
+                Err(message) => {
+                    failure = failure.or(message.lines().next());
+                    self.messages.error(message);
+                }
+            }
+        }
+        match failure {
+            Some(summary) => self.status = Some(format!("Applied {}, but {}", theme, summary)),
+            None if self.status.as_deref() == Some(&format!("Applied {}", theme)) => {
+                self.status = Some(format!("Applied {}, hooks ran", theme));
+            }
+            None => {}
+        }
     }
 
-    fn update(
-        &mut self,
-        action: crate::action::Action,
-    ) -> Result<Option<crate::action::Action>, String> {
-        Ok(None)
+    /// Shows the changes an action would write for review, or writes them
+    /// right away when there are none
+    fn review(&mut self, action: Action) -> Result<(), String> {
+        let changes = match &action {
+            Action::ApplyTheme(path) => self
+                .alacritty_config
+                .apply_changes(&AlacrittyTheme::new(path.clone()), self.config.overlay())?,
+            Action::RestoreBackup => vec![self.alacritty_config.restore_change()?],
+            Action::MigrateImport => vec![self.alacritty_config.migrate_change()?],
+            Action::InstallOverlay => self.alacritty_config.install_overlay_changes()?,
+            Action::EnableLiveReload => vec![self.alacritty_config.live_config_reload_change()],
+            Action::Adopt => self.alacritty_config.adopt_changes(None)?,
+            Action::AdoptAs(name) => self.alacritty_config.adopt_changes(Some(name))?,
+            _ => Vec::new(),
+        };
+
+        let review = ReviewChanges::new(action.clone(), &changes);
+        match review.is_empty() {
+            true => self.write_changes(action),
+            false => {
+                self.review = Some(review);
+                Ok(())
+            }
+        }
     }
 
-    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
-        todo!()
+    /// Runs an action changing the Alacritty configuration
+    fn write_changes(&mut self, action: Action) -> Result<(), String> {
+        match action {
+            Action::ApplyTheme(path) => {
+                self.apply_theme(&path)?;
+                self.themes_tab.select_theme(&path);
+                Ok(())
+            }
+            Action::RestoreBackup => self.restore_backup(),
+            Action::MigrateImport => self.migrate_import(),
+            Action::InstallOverlay => self.install_overlay(),
+            Action::EnableLiveReload => self.enable_live_reload(),
+            Action::Adopt => self.adopt(None),
+            Action::AdoptAs(name) => self.adopt(Some(&name)),
+            _ => Ok(()),
+        }
     }
-}
 
-impl App {
-    pub fn run(&mut self, terminal: &mut tui::Tui) -> io::Result<()> {
-        while !self.exit {
-            terminal.draw(|frame| self.render_frame(frame))?;
-            self.handle_events()?;
+    fn restore_backup(&mut self) -> Result<(), String> {
+        self.alacritty_config.restore_backup()?;
+        self.notify(format!(
+            "Restored {} from {}",
+            self.alacritty_config.path.display(),
+            self.alacritty_config.backup_path().display()
+        ));
+
+        Ok(())
+    }
+
+    fn migrate_import(&mut self) -> Result<(), String> {
+        if !self.alacritty_config.migrate_to_general()? {
+            self.notify("The imports are already under [general]".to_string());
+            return Ok(());
+        }
+
+        self.alacritty_config.save()?;
+        self.notify(format!(
+            "Moved the imports of {} under [general]",
+            self.alacritty_config.path.display()
+        ));
+
+        Ok(())
+    }
+
+    fn install_overlay(&mut self) -> Result<(), String> {
+        let added = self.alacritty_config.install_overlay()?;
+        self.config.enable_overlay()?;
+
+        let overlay = self.alacritty_config.overlay_path();
+        match added {
+            true => self.notify(format!(
+                "{} now imports {}, themes are applied there from now on",
+                self.alacritty_config.path.display(),
+                overlay.display()
+            )),
+            false => self.notify(format!(
+                "{} already imports {}",
+                self.alacritty_config.path.display(),
+                overlay.display()
+            )),
+        }
+
+        Ok(())
+    }
+
+    fn enable_live_reload(&mut self) -> Result<(), String> {
+        if !self.alacritty_config.enable_live_config_reload() {
+            self.notify("live_config_reload is already on".to_string());
+            return Ok(());
+        }
+
+        self.alacritty_config.save()?;
+        self.notify(format!(
+            "Turned live_config_reload on in {}, restart Alacritty once for it to take effect",
+            self.alacritty_config.path.display()
+        ));
+
+        Ok(())
+    }
+
+    fn adopt(&mut self, name: Option<&str>) -> Result<(), String> {
+        let theme = self.alacritty_config.adopt(name)?;
+        self.notify(format!(
+            "Moved the colours of {} into the theme {}",
+            self.alacritty_config.path.display(),
+            theme.name
+        ));
+        self.themes_tab.add_theme(theme);
+
+        Ok(())
+    }
+
+    /// Imports the schemes of a file, letting the user pick among them when
+    /// there are several
+    fn import(&mut self, path: &Path) -> Result<(), String> {
+        let Imported { themes, warnings } = import::read(path, None)?;
+        for warning in &warnings {
+            self.messages.error(warning);
+        }
+
+        match themes.len() {
+            0 | 1 => self.import_themes(themes),
+            _ => self.import_picker = Some(ImportPicker::new(themes)),
         }
 
         Ok(())
     }
 
-    fn render_frame(&self, frame: &mut Frame) {
+    /// Saves the imported schemes as themes, going on past the ones that
+    /// cannot be saved
+    fn import_themes(&mut self, themes: Vec<ImportedTheme>) {
+        if themes.is_empty() {
+            self.notify("Nothing imported".to_string());
+        }
+        for theme in themes {
+            match import::save(&theme) {
+                Ok(saved) => {
+                    if !theme.unmapped.is_empty() {
+                        self.messages.info(&format!(
+                            "{} left out {}",
+                            theme.name,
+                            theme.unmapped.join(", ")
+                        ));
+                    }
+                    self.notify(format!(
+                        "Imported {} into {}",
+                        theme.name,
+                        saved.path.display()
+                    ));
+                    self.themes_tab.add_theme(saved);
+                }
+                Err(error) => {
+                    self.messages.error(&error);
+                    self.status = Some(error);
+                }
+            }
+        }
     }
 
-    fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            // it's important to check that the event is a key press event as
-            // crossterm also emits key release and repeat events on Windows.
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event)
+    fn export(&mut self, format: &str) -> Result<(), String> {
+        let Some(theme) = self.themes_tab.selected_theme().cloned() else {
+            return Ok(());
+        };
+
+        let path = export::export_to_file(&theme, export::exporter(format)?)?;
+        self.notify(format!("Exported {} to {}", theme.name, path.display()));
+
+        Ok(())
+    }
+
+    fn switch_tab(&mut self, offset: usize) {
+        let index = Tab::ALL
+            .iter()
+            .position(|tab| *tab == self.selected_tab)
+            .unwrap_or_default();
+        self.selected_tab = Tab::ALL[(index + offset) % Tab::ALL.len()];
+    }
+
+    /// Index of the tab whose title is drawn at the given column
+    fn tab_at(&self, column: u16) -> Option<usize> {
+        let mut start = self.tabs_area.x;
+        for (index, tab) in Tab::ALL.iter().enumerate() {
+            // Titles are padded with a space on each side and separated by a
+            // one column divider
+            let end = start + tab.title().chars().count() as u16 + 2;
+            if (start..end).contains(&column) {
+                return Some(index);
             }
-            _ => {}
+            start = end + 1;
+        }
+
+        None
+    }
+
+    fn toggle_favorite(&mut self) -> Result<(), String> {
+        let Some(theme) = self.themes_tab.selected_theme() else {
+            return Ok(());
         };
 
+        let name = theme.name.clone();
+        self.config.toggle_favorite(&name)?;
+        self.themes_tab.set_favorites(self.config.favorites());
+
         Ok(())
     }
+}
+
+impl Component for App {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>, String> {
+        // it's important to check that the event is a key press event as
+        // crossterm also emits key release and repeat events on Windows.
+        if key.kind != KeyEventKind::Press {
+            return Ok(None);
+        }
+
+        if let Some(review) = &mut self.review {
+            return review.handle_key_events(key);
+        }
+        if let Some(import_picker) = &mut self.import_picker {
+            return import_picker.handle_key_events(key);
+        }
+        if let Some(export_dialog) = &mut self.export_dialog {
+            return export_dialog.handle_key_events(key);
+        }
+        if let Some(command_palette) = &mut self.command_palette {
+            return command_palette.handle_key_events(key);
+        }
+
+        if self.selected_tab == Tab::Themes && self.themes_tab.captures_keys() {
+            return self.themes_tab.handle_key_events(key);
+        }
+
+        let scope = self.selected_tab.scope();
+        if self.help.is_some() {
+            // Only closing the overlay is possible while it is shown, so no
+            // count or chord builds up behind it
+            return Ok(match self.keymap.resolve(scope, &[Key::from_event(&key)]) {
+                Resolution::Action(Action::Help | Action::Quit) => Some(Action::Help),
+                _ if key.code == KeyCode::Esc => Some(Action::Help),
+                _ => None,
+            });
+        }
+
+        if let KeyCode::Char(digit @ '0'..='9') = key.code {
+            // Digits that are not bound to anything build up a count, but a
+            // leading zero is never one
+            let starts_count = digit != '0' || self.count.is_some();
+            let unbound = matches!(
+                self.keymap.resolve(scope, &[Key::from_event(&key)]),
+                Resolution::Unbound
+            );
+            if self.pending_keys.is_empty() && starts_count && unbound {
+                let digit = digit.to_digit(10).unwrap_or_default() as usize;
+                let count = self.count.unwrap_or(0).saturating_mul(10);
+                self.count = Some(count.saturating_add(digit));
+                return Ok(None);
+            }
+        }
+
+        self.pending_keys.push(Key::from_event(&key));
+        let mut resolution = self.keymap.resolve(scope, &self.pending_keys);
+        if let Resolution::Unbound = resolution {
+            // A key that breaks a chord may still start a new one
+            if self.pending_keys.len() > 1 {
+                self.pending_keys = vec![Key::from_event(&key)];
+                resolution = self.keymap.resolve(scope, &self.pending_keys);
+            }
+        }
+
+        let action = match resolution {
+            Resolution::Pending => return Ok(None),
+            Resolution::Action(action) => Some(action),
+            Resolution::Unbound => None,
+        };
+        self.pending_keys.clear();
+
+        let action = match (action, self.count.take()) {
+            (Some(Action::SelectFirst | Action::SelectLast), Some(count)) => {
+                Some(Action::SelectLine(count))
+            }
+            (Some(action), Some(count)) if action.is_motion() => {
+                Some(Action::Repeat(count, Box::new(action)))
+            }
+            (action, _) => action,
+        };
+
+        Ok(action)
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<Action>, String> {
+        if self.help.is_some()
+            || self.command_palette.is_some()
+            || self.review.is_some()
+            || self.import_picker.is_some()
+            || self.export_dialog.is_some()
+        {
+            return Ok(None);
+        }
+
+        if mouse.row == self.tabs_area.y {
+            return Ok(match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    self.tab_at(mouse.column).map(Action::SelectTab)
+                }
+                _ => None,
+            });
+        }
+
+        match self.selected_tab {
+            Tab::Themes => self.themes_tab.handle_mouse_events(mouse),
+            Tab::Messages => Ok(None),
+        }
+    }
+
+    fn handle_events(&mut self, event: Option<Event>) -> Result<Option<Action>, String> {
+        let action = match event {
+            Some(Event::Key(key_event)) => self.handle_key_events(key_event)?,
+            Some(Event::Mouse(mouse_event)) => self.handle_mouse_events(mouse_event)?,
+            Some(Event::Resize(width, height)) => Some(Action::Resize(width, height)),
+            _ => None,
+        };
+
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, String> {
+        match action {
+            Action::Quit => self.exit = true,
+            Action::Help => {
+                self.pending_keys.clear();
+                self.count = None;
+                self.help = match self.help {
+                    Some(_) => None,
+                    None => Some(Help::new(&self.keymap, self.selected_tab.scope())),
+                }
+            }
+            Action::Resize(_, _) => {}
+            Action::SelectTab(index) => {
+                if let Some(tab) = Tab::ALL.get(index) {
+                    self.selected_tab = *tab;
+                }
+            }
+            Action::NextTab => self.switch_tab(1),
+            Action::PreviousTab => self.switch_tab(Tab::ALL.len() - 1),
+            Action::CommandPalette => {
+                self.command_palette = match self.command_palette {
+                    Some(_) => None,
+                    None => Some(CommandPalette::new(
+                        self.themes_tab.themes().to_vec(),
+                        self.command_history.clone(),
+                    )),
+                }
+            }
+            Action::RunCommand(line) => {
+                self.command_palette = None;
+                if line.is_empty() {
+                    return Ok(None);
+                }
+
+                command_palette::remember(&mut self.command_history, &line);
+                return Ok(Some(
+                    command_palette::parse(&line, self.themes_tab.themes())
+                        .unwrap_or_else(Action::Error),
+                ));
+            }
+            Action::HooksRan(theme, results) => self.hooks_ran(&theme, results),
+            Action::Error(error) => {
+                self.messages.error(&error);
+                self.status = Some(error);
+            }
+            Action::EditFile(path) => self.editing = Some(path),
+            Action::Import => {
+                let error = "`import` takes the path of the file to import".to_string();
+                return Ok(Some(Action::Error(error)));
+            }
+            Action::ImportFile(path) => {
+                if let Err(error) = self.import(&path) {
+                    return Ok(Some(Action::Error(error)));
+                }
+            }
+            Action::Export => {
+                self.export_dialog = match (&self.export_dialog, self.themes_tab.selected_theme()) {
+                    (None, Some(theme)) => Some(ExportDialog::new(theme.name.clone())),
+                    _ => None,
+                }
+            }
+            Action::ExportAs(format) => {
+                self.export_dialog = None;
+                if let Err(error) = self.export(&format) {
+                    return Ok(Some(Action::Error(error)));
+                }
+            }
+            Action::ImportThemes(themes) => {
+                self.import_picker = None;
+                self.import_themes(themes);
+            }
+            Action::ApplyTheme(_)
+            | Action::RestoreBackup
+            | Action::MigrateImport
+            | Action::InstallOverlay
+            | Action::EnableLiveReload
+            | Action::Adopt
+            | Action::AdoptAs(_) => {
+                let result = match self.config.review() {
+                    true => self.review(action),
+                    false => self.write_changes(action),
+                };
+                if let Err(error) = result {
+                    return Ok(Some(Action::Error(error)));
+                }
+            }
+            Action::Confirm(action) => {
+                self.review = None;
+                if let Err(error) = self.write_changes(*action) {
+                    return Ok(Some(Action::Error(error)));
+                }
+            }
+            Action::DiscardChanges => {
+                self.review = None;
+                self.notify("Discarded the changes".to_string());
+            }
+            Action::Repeat(count, action) => {
+                for _ in 0..count {
+                    if let Some(next) = self.update((*action).clone())? {
+                        return Ok(Some(next));
+                    }
+                }
+            }
+            Action::ToggleFavorite => {
+                if let Err(error) = self.toggle_favorite() {
+                    return Ok(Some(Action::Error(error)));
+                }
+            }
+            action => return self.themes_tab.update(action),
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(1),
+                Constraint::Percentage(100),
+                Constraint::Length(1),
+            ])
+            .split(area);
+
+        let tabs = Tabs::new(Tab::ALL.iter().map(Tab::title).collect::<Vec<_>>())
+            .style(Style::new().bg(Color::Green).black())
+            .highlight_style(Style::new().yellow())
+            .select(self.selected_tab as usize)
+            .divider(symbols::DOT);
+        f.render_widget(tabs, layout[0]);
+        self.tabs_area = layout[0];
+
+        match self.selected_tab {
+            Tab::Themes => self.themes_tab.draw(f, layout[1])?,
+            Tab::Messages => self.messages.draw(f, layout[1])?,
+        }
+
+        let typed = self
+            .count
+            .iter()
+            .map(usize::to_string)
+            .chain(self.pending_keys.iter().map(Key::label))
+            .collect::<Vec<_>>();
+        let status = match (typed.is_empty(), &self.status) {
+            (false, _) => typed.join(" "),
+            (true, Some(status)) => status.clone(),
+            (true, None) => match self.keymap.label_for(Scope::Global, &Action::Help) {
+                Some(help) => format!("Press {} for help", help),
+                None => String::new(),
+            },
+        };
+        let warning = self.live_reload_warning().unwrap_or_default();
+        let status_bar = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Fill(1),
+                Constraint::Length(warning.chars().count() as u16),
+            ])
+            .split(layout[2]);
+        f.render_widget(Paragraph::new(status).dark_gray(), status_bar[0]);
+        f.render_widget(Paragraph::new(warning).yellow(), status_bar[1]);
+
+        if let Some(help) = &mut self.help {
+            help.draw(f, area)?;
+        }
+        if let Some(command_palette) = &mut self.command_palette {
+            command_palette.draw(f, area)?;
+        }
+        if let Some(import_picker) = &mut self.import_picker {
+            import_picker.draw(f, area)?;
+        }
+        if let Some(export_dialog) = &mut self.export_dialog {
+            export_dialog.draw(f, area)?;
+        }
+        if let Some(review) = &mut self.review {
+            review.draw(f, area)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Opens a file in `$VISUAL` or `$EDITOR` and waits for the editor to exit
+fn open_in_editor(path: &Path) -> Result<(), String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| match cfg!(windows) {
+            true => "notepad".to_string(),
+            false => "vi".to_string(),
+        });
+
+    // The variable may hold arguments as well, as in `code --wait`
+    let mut words = editor.split_whitespace();
+    let program = words.next().ok_or("$EDITOR is empty")?;
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|e| format!("Unable to run {}: {}", program, e))?;
 
-    fn handle_key_event(&mut self, key_event: KeyEvent) {
+    match status.success() {
+        true => Ok(()),
+        false => Err(format!("{} exited with {}", program, status)),
     }
 }
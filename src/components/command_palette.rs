@@ -0,0 +1,246 @@
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use super::help::centered_rect;
+use super::Component;
+use crate::action::{Action, COMMANDS};
+use crate::alacritty::{self, AlacrittyTheme};
+use crate::fuzzy;
+
+/// Number of command lines remembered by the palette
+const HISTORY_SIZE: usize = 20;
+
+/// Name of the command whose argument completes to a theme name
+const APPLY: &str = "apply";
+
+/// An entry proposed by the palette for the current input
+struct Candidate {
+    /// What the input becomes when the candidate is completed
+    line: String,
+    label: String,
+    detail: String,
+}
+
+/// Overlay to run any bindable action by name, with fuzzy matching, theme name
+/// completion for `apply` and a history of the last command lines
+pub struct CommandPalette {
+    input: String,
+    themes: Vec<AlacrittyTheme>,
+    history: Vec<String>,
+    candidates: Vec<Candidate>,
+    state: ListState,
+}
+
+impl CommandPalette {
+    pub fn new(themes: Vec<AlacrittyTheme>, history: Vec<String>) -> Self {
+        let mut palette = Self {
+            input: String::new(),
+            themes,
+            history,
+            candidates: Vec::new(),
+            state: ListState::default(),
+        };
+        palette.refresh();
+
+        palette
+    }
+
+    /// Recomputes the candidates after the input changed
+    fn refresh(&mut self) {
+        self.candidates = match self.input.split_once(' ') {
+            Some((APPLY, name)) => self.theme_candidates(name.trim_start()),
+            Some(_) => Vec::new(),
+            None => self.command_candidates(),
+        };
+        self.state
+            .select((!self.candidates.is_empty()).then_some(0));
+    }
+
+    fn command_candidates(&self) -> Vec<Candidate> {
+        let mut candidates = Vec::new();
+        if self.input.is_empty() {
+            candidates.extend(self.history.iter().map(|line| Candidate {
+                line: line.clone(),
+                label: line.clone(),
+                detail: "recent".to_string(),
+            }));
+        }
+
+        let mut commands: Vec<(i64, &Action)> = COMMANDS
+            .iter()
+            .filter(|action| **action != Action::CommandPalette)
+            .filter_map(|action| Some((fuzzy::score(&self.input, action.name())?, action)))
+            .collect();
+        commands.sort_by_key(|(score, _)| -score);
+
+        candidates.extend(commands.into_iter().map(|(_, action)| Candidate {
+            line: action.name().to_string(),
+            label: action.name().to_string(),
+            detail: action.description().to_string(),
+        }));
+
+        candidates
+    }
+
+    fn theme_candidates(&self, name: &str) -> Vec<Candidate> {
+        let mut themes: Vec<(i64, &AlacrittyTheme)> = self
+            .themes
+            .iter()
+            .filter_map(|theme| Some((fuzzy::score(name, &theme.name)?, theme)))
+            .collect();
+        themes.sort_by_key(|(score, _)| -score);
+
+        themes
+            .into_iter()
+            .map(|(_, theme)| Candidate {
+                line: format!("{} {}", APPLY, theme.name),
+                label: theme.name.clone(),
+                detail: String::new(),
+            })
+            .collect()
+    }
+
+    fn selected(&self) -> Option<&Candidate> {
+        self.state
+            .selected()
+            .and_then(|selected| self.candidates.get(selected))
+    }
+
+    /// Completes the input with the selected candidate, leaving room for the
+    /// theme name after `apply`
+    fn complete(&mut self) {
+        let Some(candidate) = self.selected() else {
+            return;
+        };
+
+        self.input = match candidate.line.as_str() {
+            APPLY => format!("{} ", APPLY),
+            line => line.to_string(),
+        };
+        self.refresh();
+    }
+
+    /// Line to run when Enter is pressed: the selected candidate when the
+    /// input is still being matched against, the input as typed otherwise
+    fn submitted_line(&self) -> String {
+        match (self.selected(), self.input.split_once(' ')) {
+            (Some(candidate), None) | (Some(candidate), Some((APPLY, _))) => candidate.line.clone(),
+            _ => self.input.trim().to_string(),
+        }
+    }
+}
+
+/// Remembers a command line, most recent first and without duplicates
+pub fn remember(history: &mut Vec<String>, line: &str) {
+    history.retain(|previous| previous != line);
+    history.insert(0, line.to_string());
+    history.truncate(HISTORY_SIZE);
+}
+
+/// Turns a command line such as `apply Gruvbox-Dark` or `random` into the
+/// action it stands for
+pub fn parse(line: &str, themes: &[AlacrittyTheme]) -> Result<Action, String> {
+    let (name, argument) = match line.trim().split_once(' ') {
+        Some((name, argument)) => (name, argument.trim()),
+        None => (line.trim(), ""),
+    };
+
+    let action = Action::from_name(name).ok_or_else(|| format!("Unknown command `{}`", name))?;
+    match (action, argument) {
+        (Action::Apply, "") => Ok(Action::Apply),
+        (Action::Apply, theme_name) => themes
+            .iter()
+            .find(|theme| theme.name.eq_ignore_ascii_case(theme_name))
+            .map(|theme| Action::ApplyTheme(theme.path.clone()))
+            .ok_or_else(|| format!("Unknown theme `{}`", theme_name)),
+        (Action::Adopt, theme_name) if !theme_name.is_empty() => {
+            Ok(Action::AdoptAs(theme_name.to_string()))
+        }
+        (Action::Export, format) if !format.is_empty() => Ok(Action::ExportAs(format.to_string())),
+        (Action::Import, "") => Err("`import` takes the path of the file to import".to_string()),
+        (Action::Import, path) => Ok(Action::ImportFile(alacritty::resolve_import(
+            path,
+            Path::new(""),
+        ))),
+        (action, "") => Ok(action),
+        (_, _) => Err(format!("`{}` takes no argument", name)),
+    }
+}
+
+impl Component for CommandPalette {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>, String> {
+        match key.code {
+            KeyCode::Esc => return Ok(Some(Action::CommandPalette)),
+            KeyCode::Enter => return Ok(Some(Action::RunCommand(self.submitted_line()))),
+            KeyCode::Tab => self.complete(),
+            KeyCode::Down => {
+                if let Some(selected) = self.state.selected() {
+                    let last = self.candidates.len().saturating_sub(1);
+                    self.state.select(Some((selected + 1).min(last)));
+                }
+            }
+            KeyCode::Up => {
+                if let Some(selected) = self.state.selected() {
+                    self.state.select(Some(selected.saturating_sub(1)));
+                }
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.refresh();
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.refresh();
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
+        let popup = centered_rect(70, 16, area);
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(3), Constraint::Fill(1)])
+            .split(popup);
+
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            Paragraph::new(format!(":{}", self.input)).block(
+                Block::default()
+                    .title("")
+                    .title("⌨️  Command (Tab to complete, Esc to close)")
+                    .title_style(Style::new().white())
+                    .borders(Borders::ALL)
+                    .border_style(Style::new().yellow())
+                    .border_type(BorderType::Rounded),
+            ),
+            layout[0],
+        );
+
+        let items = self.candidates.iter().map(|candidate| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<24}", candidate.label)),
+                Span::raw(candidate.detail.clone()).dark_gray(),
+            ]))
+        });
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::new().yellow())
+                    .border_type(BorderType::Rounded),
+            )
+            .highlight_style(Style::new().bg(Color::Cyan).black());
+        f.render_stateful_widget(list, layout[1], &mut self.state);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState};
+use ratatui::Frame;
+
+use super::help::centered_rect;
+use super::Component;
+use crate::action::Action;
+use crate::export::EXPORTERS;
+
+/// Overlay to pick the format the selected theme is exported to
+pub struct ExportDialog {
+    theme: String,
+    state: ListState,
+}
+
+impl ExportDialog {
+    pub fn new(theme: String) -> Self {
+        Self {
+            theme,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+}
+
+impl Component for ExportDialog {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>, String> {
+        match key.code {
+            KeyCode::Enter => {
+                let exporter = self
+                    .state
+                    .selected()
+                    .and_then(|selected| EXPORTERS.get(selected));
+                return Ok(exporter.map(|exporter| Action::ExportAs(exporter.name().to_string())));
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(Some(Action::Export)),
+            KeyCode::Down | KeyCode::Char('j') => {
+                let selected = self.state.selected().unwrap_or_default();
+                self.state
+                    .select(Some((selected + 1).min(EXPORTERS.len() - 1)));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                let selected = self.state.selected().unwrap_or_default();
+                self.state.select(Some(selected.saturating_sub(1)));
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
+        let popup = centered_rect(60, EXPORTERS.len() as u16 + 2, area);
+        let items = EXPORTERS.iter().map(|exporter| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<20}", exporter.name())),
+                Span::raw(format!("{}{}", self.theme, exporter.extension())).dark_gray(),
+            ]))
+        });
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title("")
+                    .title(format!(
+                        "📤 Export {} (Enter to export, Esc to close)",
+                        self.theme
+                    ))
+                    .title_style(Style::new().white())
+                    .borders(Borders::ALL)
+                    .border_style(Style::new().yellow())
+                    .border_type(BorderType::Rounded),
+            )
+            .highlight_style(Style::new().bg(Color::Cyan).black());
+
+        f.render_widget(Clear, popup);
+        f.render_stateful_widget(list, popup, &mut self.state);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Row, Table};
+use ratatui::Frame;
+
+use super::Component;
+use crate::action::Action;
+use crate::keymap::{Keymap, Scope};
+
+/// Overlay listing the key bindings of the focused component and the global
+/// ones, built from the keymap used for dispatching
+pub struct Help {
+    sections: Vec<(Scope, Vec<(String, Action)>)>,
+}
+
+impl Help {
+    pub fn new(keymap: &Keymap, focused: Scope) -> Self {
+        // The global bindings come last, listed once when nothing more
+        // specific is focused
+        let scopes = match focused {
+            Scope::Global => vec![Scope::Global],
+            _ => vec![focused, Scope::Global],
+        };
+        let sections = scopes
+            .into_iter()
+            .map(|scope| {
+                // Keys bound to the same action share a single row
+                let mut rows: Vec<(String, Action)> = Vec::new();
+                for binding in keymap.bindings(scope) {
+                    match rows
+                        .iter_mut()
+                        .find(|(_, action)| *action == binding.action)
+                    {
+                        Some((keys, _)) => {
+                            keys.push_str(", ");
+                            keys.push_str(&binding.key_label());
+                        }
+                        None => rows.push((binding.key_label(), binding.action.clone())),
+                    }
+                }
+
+                (scope, rows)
+            })
+            .collect();
+
+        Self { sections }
+    }
+}
+
+/// Returns a rectangle of the given size centered inside `area`
+pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+            Constraint::Fill(1),
+            Constraint::Length(height.min(area.height)),
+            Constraint::Fill(1),
+        ])
+        .split(area)[1];
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Fill(1),
+            Constraint::Length(width.min(area.width)),
+            Constraint::Fill(1),
+        ])
+        .split(vertical)[1]
+}
+
+impl Component for Help {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
+        let mut rows = Vec::new();
+        for (scope, bindings) in &self.sections {
+            if !rows.is_empty() {
+                rows.push(Row::new(vec![""]));
+            }
+            rows.push(Row::new(vec![Line::from(scope.title()).bold().yellow()]));
+            for (keys, action) in bindings {
+                rows.push(Row::new(vec![
+                    Line::from(keys.clone()).cyan(),
+                    Line::from(action.description()),
+                ]));
+            }
+        }
+
+        let height = rows.len() as u16 + 2;
+        let popup = centered_rect(50, height, area);
+        let table = Table::new(rows, [Constraint::Length(16), Constraint::Fill(1)]).block(
+            Block::default()
+                .title("")
+                .title("❓ Help (Esc to close)")
+                .title_style(Style::new().white())
+                .borders(Borders::ALL)
+                .border_style(Style::new().yellow())
+                .border_type(BorderType::Rounded),
+        );
+
+        f.render_widget(Clear, popup);
+        f.render_widget(table, popup);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scopes(help: &Help) -> Vec<Scope> {
+        help.sections.iter().map(|(scope, _)| *scope).collect()
+    }
+
+    #[test]
+    fn lists_the_focused_bindings_then_the_global_ones() {
+        let keymap = Keymap::default();
+
+        assert_eq!(
+            scopes(&Help::new(&keymap, Scope::Themes)),
+            [Scope::Themes, Scope::Global]
+        );
+        assert_eq!(scopes(&Help::new(&keymap, Scope::Global)), [Scope::Global]);
+    }
+
+    #[test]
+    fn keys_of_the_same_action_share_a_row() {
+        let help = Help::new(&Keymap::default(), Scope::Themes);
+
+        let (_, themes) = &help.sections[0];
+        assert!(themes.contains(&("↓, j".to_string(), Action::SelectNext)));
+    }
+}
@@ -0,0 +1,121 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState};
+use ratatui::Frame;
+
+use super::help::centered_rect;
+use super::Component;
+use crate::action::Action;
+use crate::import::ImportedTheme;
+
+/// Overlay to pick which of the schemes of an imported file become themes
+pub struct ImportPicker {
+    themes: Vec<ImportedTheme>,
+    picked: Vec<bool>,
+    state: ListState,
+}
+
+impl ImportPicker {
+    pub fn new(themes: Vec<ImportedTheme>) -> Self {
+        Self {
+            picked: vec![false; themes.len()],
+            themes,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    /// The picked schemes, or the highlighted one when none is picked
+    fn submitted(&self) -> Vec<ImportedTheme> {
+        let picked: Vec<ImportedTheme> = self
+            .themes
+            .iter()
+            .zip(&self.picked)
+            .filter(|(_, picked)| **picked)
+            .map(|(theme, _)| theme.clone())
+            .collect();
+        if !picked.is_empty() {
+            return picked;
+        }
+
+        self.state
+            .selected()
+            .and_then(|selected| self.themes.get(selected))
+            .cloned()
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Component for ImportPicker {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>, String> {
+        match key.code {
+            KeyCode::Enter => return Ok(Some(Action::ImportThemes(self.submitted()))),
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(Some(Action::ImportThemes(Vec::new()))),
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.themes.len().saturating_sub(1);
+                let selected = self.state.selected().unwrap_or_default();
+                self.state.select(Some((selected + 1).min(last)));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                let selected = self.state.selected().unwrap_or_default();
+                self.state.select(Some(selected.saturating_sub(1)));
+            }
+            KeyCode::Char(' ') => {
+                if let Some(picked) = self
+                    .state
+                    .selected()
+                    .and_then(|selected| self.picked.get_mut(selected))
+                {
+                    *picked = !*picked;
+                }
+            }
+            KeyCode::Char('a') => {
+                let all = !self.picked.iter().all(|picked| *picked);
+                self.picked.iter_mut().for_each(|picked| *picked = all);
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
+        let popup = centered_rect(70, 20, area);
+        let items = self.themes.iter().zip(&self.picked).map(|(theme, picked)| {
+            let mut spans = vec![Span::raw(match picked {
+                true => "[x] ",
+                false => "[ ] ",
+            })];
+            spans.push(Span::raw(format!("{:<36}", theme.name)));
+            // A swatch of the normal colours
+            spans.extend(
+                theme
+                    .palette
+                    .normal
+                    .iter()
+                    .flatten()
+                    .map(|color| Span::raw("  ").bg(Color::Rgb(color.r, color.g, color.b))),
+            );
+            ListItem::new(Line::from(spans))
+        });
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title("")
+                    .title("📥 Import schemes (Space to pick, a for all, Enter to import)")
+                    .title_style(Style::new().white())
+                    .borders(Borders::ALL)
+                    .border_style(Style::new().yellow())
+                    .border_type(BorderType::Rounded),
+            )
+            .highlight_style(Style::new().bg(Color::Cyan).black());
+
+        f.render_widget(Clear, popup);
+        f.render_stateful_widget(list, popup, &mut self.state);
+
+        Ok(())
+    }
+}
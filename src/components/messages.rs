@@ -0,0 +1,56 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
+use ratatui::Frame;
+
+use super::Component;
+
+/// Log of every status message shown during the session, newest last
+#[derive(Default)]
+pub struct Messages {
+    entries: Vec<(bool, String)>,
+}
+
+impl Messages {
+    pub fn info(&mut self, message: &str) {
+        self.entries.push((false, message.to_string()));
+    }
+
+    pub fn error(&mut self, message: &str) {
+        self.entries.push((true, message.to_string()));
+    }
+}
+
+impl Component for Messages {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
+        let lines: Vec<Line> = self
+            .entries
+            .iter()
+            .flat_map(|(error, message)| {
+                message.lines().map(move |line| match error {
+                    true => Line::from(line.to_string()).red(),
+                    false => Line::from(line.to_string()),
+                })
+            })
+            .collect();
+
+        // Keep the latest messages in view
+        let visible = area.height.saturating_sub(2) as usize;
+        let scroll = lines.len().saturating_sub(visible) as u16;
+
+        f.render_widget(
+            Paragraph::new(lines).scroll((scroll, 0)).block(
+                Block::default()
+                    .title("")
+                    .title("📜 Messages")
+                    .title_style(Style::new().white())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            ),
+            area,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
+use ratatui::Frame;
+
+use super::Component;
+use crate::theme::{Palette, Rgb, SAMPLE};
+
+/// Width of the mock terminal drawn at the top of the preview
+const SAMPLE_WIDTH: usize = 44;
+
+/// Shows a sample of the selected theme and the list of its colours
+pub struct Preview {
+    theme: Option<PathBuf>,
+    palette: Result<Palette, String>,
+    scroll: u16,
+    line_count: u16,
+}
+
+impl Default for Preview {
+    fn default() -> Self {
+        Self {
+            theme: None,
+            palette: Ok(Palette::default()),
+            scroll: 0,
+            line_count: 0,
+        }
+    }
+}
+
+impl Preview {
+    /// Switches to another theme, reloading its colours when it changed
+    pub fn show(&mut self, theme: Option<&Path>) {
+        if self.theme.as_deref() == theme {
+            return;
+        }
+
+        self.theme = theme.map(Path::to_path_buf);
+        self.palette = match theme {
+            Some(path) => Palette::load(path),
+            None => Ok(Palette::default()),
+        };
+        self.scroll = 0;
+    }
+
+    /// Reads the colours of the current theme again on the next draw
+    pub fn reload(&mut self) {
+        self.theme = None;
+    }
+
+    pub fn scroll_down(&mut self, lines: u16) {
+        self.scroll = self
+            .scroll
+            .saturating_add(lines)
+            .min(self.line_count.saturating_sub(1));
+    }
+
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    fn lines(palette: &Palette) -> Vec<Line<'static>> {
+        let color = |rgb: Option<Rgb>| match rgb {
+            Some(rgb) => Color::Rgb(rgb.r, rgb.g, rgb.b),
+            None => Color::Reset,
+        };
+        let base = Style::new()
+            .bg(color(palette.background))
+            .fg(color(palette.foreground));
+        let ansi = |index: usize| Style::new().fg(color(palette.normal[index]));
+
+        // Pads a line of the mock terminal so its background is filled
+        let sample = |spans: Vec<Span<'static>>| {
+            let width: usize = spans.iter().map(|span| span.content.chars().count()).sum();
+            let mut spans = spans;
+            spans.push(Span::raw(" ".repeat(SAMPLE_WIDTH.saturating_sub(width))));
+            Line::from(spans).style(base)
+        };
+        let swatches = |colors: &[Option<Rgb>; 8]| {
+            let mut spans = vec![Span::raw(" ")];
+            spans.extend(
+                colors
+                    .iter()
+                    .map(|rgb| Span::raw("    ").bg(color(*rgb)))
+                    .flat_map(|swatch| [swatch, Span::raw(" ")]),
+            );
+            sample(spans)
+        };
+
+        let mut lines = vec![sample(vec![])];
+        lines.extend(SAMPLE.iter().map(|spans| {
+            sample(
+                spans
+                    .iter()
+                    .map(|(text, index)| match index {
+                        Some(index) => Span::styled(*text, ansi(*index)),
+                        None => Span::raw(*text),
+                    })
+                    .collect(),
+            )
+        }));
+        lines.extend([
+            sample(vec![]),
+            swatches(&palette.normal),
+            sample(vec![]),
+            swatches(&palette.bright),
+            sample(vec![]),
+            Line::from(""),
+        ]);
+
+        for (name, rgb) in palette.entries() {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::raw("    ").bg(color(Some(rgb))),
+                Span::raw(format!(" {:<22}", name)),
+                Span::raw(rgb.to_hex()).dark_gray(),
+            ]));
+        }
+
+        lines
+    }
+}
+
+impl Component for Preview {
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
+        let lines = match &self.palette {
+            Ok(palette) => Self::lines(palette),
+            Err(error) => vec![Line::from(error.clone()).red()],
+        };
+        self.line_count = lines.len() as u16;
+
+        let preview = Paragraph::new(lines).scroll((self.scroll, 0)).block(
+            Block::new()
+                .title("")
+                .title("👁️  Preview")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded),
+        );
+        f.render_widget(preview, area);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use super::help::centered_rect;
+use super::Component;
+use crate::action::Action;
+use crate::alacritty::Change;
+
+/// Overlay showing the diff of the changes an action is about to write, which
+/// only goes ahead once confirmed
+pub struct ReviewChanges {
+    action: Action,
+    diff: Vec<String>,
+    scroll: u16,
+}
+
+impl ReviewChanges {
+    pub fn new(action: Action, changes: &[Change]) -> Self {
+        let diff = changes
+            .iter()
+            .flat_map(|change| change.diff().lines().map(String::from).collect::<Vec<_>>())
+            .collect();
+
+        Self {
+            action,
+            diff,
+            scroll: 0,
+        }
+    }
+
+    /// Whether the action would not change anything
+    pub fn is_empty(&self) -> bool {
+        self.diff.is_empty()
+    }
+}
+
+impl Component for ReviewChanges {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>, String> {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y') => {
+                return Ok(Some(Action::Confirm(Box::new(self.action.clone()))))
+            }
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => {
+                return Ok(Some(Action::DiscardChanges))
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.diff.len().saturating_sub(1) as u16;
+                self.scroll = (self.scroll + 1).min(last);
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
+        let popup = centered_rect(
+            area.width.saturating_sub(8),
+            area.height.saturating_sub(4),
+            area,
+        );
+        let lines: Vec<Line> = self
+            .diff
+            .iter()
+            .map(|line| match line {
+                _ if line.starts_with("---") || line.starts_with("+++") => {
+                    Line::from(line.clone()).bold()
+                }
+                _ if line.starts_with("@@") => Line::from(line.clone()).cyan(),
+                _ if line.starts_with('-') => Line::from(line.clone()).red(),
+                _ if line.starts_with('+') => Line::from(line.clone()).green(),
+                _ => Line::from(line.clone()),
+            })
+            .collect();
+
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            Paragraph::new(lines).scroll((self.scroll, 0)).block(
+                Block::default()
+                    .title("")
+                    .title("📝 Review changes (Enter to write, Esc to cancel)")
+                    .title_style(Style::new().white())
+                    .borders(Borders::ALL)
+                    .border_style(Style::new().yellow())
+                    .border_type(BorderType::Rounded),
+            ),
+            popup,
+        );
+
+        Ok(())
+    }
+}
@@ -1,29 +1,432 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use ratatui::layout::{Constraint, Direction, Layout};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Style};
-use ratatui::widgets::{Block, BorderType, Borders, List, ListState};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph};
 use ratatui::{style::Stylize, Frame};
-use std::io;
+
+use super::preview::Preview;
+use super::Component;
+use crate::action::Action;
+use crate::alacritty::AlacrittyTheme;
+
+/// Two clicks on the same theme closer than this apply it
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    (area.x..area.right()).contains(&column) && (area.y..area.bottom()).contains(&row)
+}
+
+/// Words that tell the variants of a theme family apart
+const VARIANT_PAIRS: [(&str, &str); 3] = [("dark", "light"), ("mocha", "latte"), ("night", "day")];
+
+/// What the key presses are currently typed into, instead of the keymap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Input {
+    Filter,
+    Search,
+    SetMark,
+    JumpToMark,
+}
 
 pub struct ThemesTab {
-    counter: usize,
+    themes: Vec<AlacrittyTheme>,
+    favorites: Vec<String>,
+    /// Indices into `themes` of the entries matching the filter
+    visible: Vec<usize>,
+    state: ListState,
+    filter: String,
+    search: String,
+    input: Option<Input>,
+    marks: HashMap<char, PathBuf>,
+    /// Number of list rows shown on screen the last time it was drawn
+    page_height: usize,
+    filter_hint: String,
+    preview: Preview,
+    /// Width of the theme list, in percent of the tab
+    split: u16,
+    resizing: bool,
+    last_click: Option<(Instant, usize)>,
+    /// Where the tab, the list and the preview were last drawn, to map mouse
+    /// events back to them
+    area: Rect,
+    list_area: Rect,
+    preview_area: Rect,
 }
 
 impl ThemesTab {
-    pub fn init() -> Self {
-        Self { counter: 0 }
+    pub fn init(themes: Vec<AlacrittyTheme>, favorites: Vec<String>, filter_hint: String) -> Self {
+        let mut tab = Self {
+            visible: (0..themes.len()).collect(),
+            themes,
+            favorites,
+            state: ListState::default(),
+            filter: String::new(),
+            search: String::new(),
+            input: None,
+            marks: HashMap::new(),
+            page_height: 0,
+            filter_hint,
+            preview: Preview::default(),
+            split: 40,
+            resizing: false,
+            last_click: None,
+            area: Rect::default(),
+            list_area: Rect::default(),
+            preview_area: Rect::default(),
+        };
+        tab.apply_filter();
+
+        tab
     }
 
-    fn render_frame(&self, frame: &mut Frame) {
-        let canvas = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![Constraint::Length(1), Constraint::Percentage(100)])
-            .split(frame.size())[1];
+    /// Whether the key presses are currently typed into the tab (filter,
+    /// search or mark name) rather than going through the keymap
+    pub fn captures_keys(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn selected_theme(&self) -> Option<&AlacrittyTheme> {
+        self.state
+            .selected()
+            .and_then(|selected| self.visible.get(selected))
+            .map(|&index| &self.themes[index])
+    }
+
+    pub fn themes(&self) -> &[AlacrittyTheme] {
+        &self.themes
+    }
 
+    /// Selects the theme with the given path, when it is not filtered out
+    pub fn select_theme(&mut self, path: &Path) {
+        let position = self
+            .visible
+            .iter()
+            .position(|&index| self.themes[index].path == path);
+        if let Some(position) = position {
+            self.select(position);
+        }
+    }
+
+    /// Adds a theme to the list and selects it
+    pub fn add_theme(&mut self, theme: AlacrittyTheme) {
+        let path = theme.path.clone();
+        self.themes.push(theme);
+        self.themes.sort_by_key(|theme| theme.name.to_lowercase());
+        self.apply_filter();
+        self.select_theme(&path);
+    }
+
+    pub fn set_favorites(&mut self, favorites: Vec<String>) {
+        self.favorites = favorites;
+    }
+
+    fn apply_filter(&mut self) {
+        let selected = self.selected_theme().map(|theme| theme.path.clone());
+        let filter = self.filter.to_lowercase();
+
+        self.visible = (0..self.themes.len())
+            .filter(|&index| self.themes[index].name.to_lowercase().contains(&filter))
+            .collect();
+
+        // Keep the same theme selected when it is still listed
+        let position = selected
+            .and_then(|path| {
+                self.visible
+                    .iter()
+                    .position(|&index| self.themes[index].path == path)
+            })
+            .or(if self.visible.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+        self.state.select(position);
+    }
+
+    /// Selects the entry at `index` of the filtered list, clamped to its bounds
+    fn select(&mut self, index: usize) {
+        match self.visible.len() {
+            0 => self.state.select(None),
+            len => self.state.select(Some(index.min(len - 1))),
+        }
+    }
+
+    fn move_down(&mut self, rows: usize) {
+        if let Some(selected) = self.state.selected() {
+            self.select(selected.saturating_add(rows));
+        }
+    }
+
+    fn move_up(&mut self, rows: usize) {
+        if let Some(selected) = self.state.selected() {
+            self.select(selected.saturating_sub(rows));
+        }
+    }
+
+    /// Position in the filtered list of the theme drawn at the given row
+    fn position_at(&self, row: u16) -> Option<usize> {
+        let inner_top = self.list_area.y + 1;
+        if row < inner_top || row >= self.list_area.bottom().saturating_sub(1) {
+            return None;
+        }
+
+        let position = self.state.offset() + (row - inner_top) as usize;
+        (position < self.visible.len()).then_some(position)
+    }
+
+    fn click(&mut self, position: usize) -> Option<Action> {
+        self.select(position);
+
+        let now = Instant::now();
+        match self.last_click.take() {
+            Some((at, previous)) if previous == position && now - at < DOUBLE_CLICK => {
+                Some(Action::Apply)
+            }
+            _ => {
+                self.last_click = Some((now, position));
+                None
+            }
+        }
+    }
+
+    fn is_match(&self, position: usize) -> bool {
+        !self.search.is_empty()
+            && self.themes[self.visible[position]]
+                .name
+                .to_lowercase()
+                .contains(&self.search.to_lowercase())
+    }
+
+    /// Selects the closest search match after (or before) the selection,
+    /// wrapping around the filtered list
+    fn jump_to_match(&mut self, forward: bool, include_selected: bool) {
+        let len = self.visible.len();
+        let Some(selected) = self.state.selected() else {
+            return;
+        };
+
+        let start = if include_selected { 0 } else { 1 };
+        let found = (start..=len)
+            .map(|offset| match forward {
+                true => (selected + offset) % len,
+                false => (selected + len - offset % len) % len,
+            })
+            .find(|&position| self.is_match(position));
+
+        if let Some(position) = found {
+            self.select(position);
+        }
+    }
+
+    fn random(&mut self) -> Option<Action> {
+        if self.visible.is_empty() {
+            return None;
+        }
+
+        let random = RandomState::new().build_hasher().finish() as usize;
+        self.select(random % self.visible.len());
+        self.selected_theme()
+            .map(|theme| Action::ApplyTheme(theme.path.clone()))
+    }
+
+    /// Selects the light variant of a dark theme or the other way around,
+    /// going by the theme names (`Gruvbox-Dark` and `Gruvbox-Light`)
+    fn toggle_light_dark(&mut self) -> Option<Action> {
+        let theme = self.selected_theme()?;
+        let name = theme.name.to_lowercase();
+
+        let counterpart = VARIANT_PAIRS
+            .iter()
+            .flat_map(|(a, b)| [(a, b), (b, a)])
+            .filter_map(|(from, to)| {
+                let at = name.rfind(from)?;
+                Some(format!("{}{}{}", &name[..at], to, &name[at + from.len()..]))
+            })
+            .find_map(|wanted| {
+                self.themes
+                    .iter()
+                    .find(|theme| theme.name.to_lowercase() == wanted)
+            });
+
+        match counterpart {
+            Some(counterpart) => {
+                let path = counterpart.path.clone();
+                self.select_theme(&path);
+                Some(Action::ApplyTheme(path))
+            }
+            None => Some(Action::Error(format!(
+                "No light or dark variant of {} found",
+                theme.name
+            ))),
+        }
+    }
+
+    fn set_mark(&mut self, name: char) -> Option<Action> {
+        let path = self.selected_theme()?.path.clone();
+        self.marks.insert(name, path);
+
+        None
+    }
+
+    fn jump_to_mark(&mut self, name: char) -> Option<Action> {
+        let Some(path) = self.marks.get(&name) else {
+            return Some(Action::Error(format!("Mark '{}' is not set", name)));
+        };
+
+        let position = self
+            .visible
+            .iter()
+            .position(|&index| self.themes[index].path == *path);
+        match position {
+            Some(position) => {
+                self.select(position);
+                None
+            }
+            None => Some(Action::Error(format!(
+                "Mark '{}' is hidden by the filter",
+                name
+            ))),
+        }
+    }
+}
+
+impl Component for ThemesTab {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>, String> {
+        let Some(input) = self.input else {
+            return Ok(None);
+        };
+
+        match (input, key.code) {
+            (Input::SetMark, KeyCode::Char(c)) => {
+                self.input = None;
+                return Ok(self.set_mark(c));
+            }
+            (Input::JumpToMark, KeyCode::Char(c)) => {
+                self.input = None;
+                return Ok(self.jump_to_mark(c));
+            }
+            (Input::SetMark | Input::JumpToMark, _) => self.input = None,
+
+            (Input::Filter, KeyCode::Char(c)) => self.filter.push(c),
+            (Input::Search, KeyCode::Char(c)) => self.search.push(c),
+            (Input::Filter, KeyCode::Backspace) => {
+                self.filter.pop();
+            }
+            (Input::Search, KeyCode::Backspace) => {
+                self.search.pop();
+            }
+            (_, KeyCode::Enter) => self.input = None,
+            (Input::Filter, KeyCode::Esc) => {
+                self.filter.clear();
+                self.input = None;
+            }
+            (Input::Search, KeyCode::Esc) => {
+                self.search.clear();
+                self.input = None;
+            }
+            _ => {}
+        }
+
+        match input {
+            Input::Filter => self.apply_filter(),
+            Input::Search => self.jump_to_match(true, true),
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<Action>, String> {
+        let in_list = contains(self.list_area, mouse.column, mouse.row);
+        let in_preview = contains(self.preview_area, mouse.column, mouse.row);
+        // Either of the two borders facing each other grabs the split
+        let on_split =
+            mouse.column + 1 == self.list_area.right() || mouse.column == self.preview_area.x;
+
+        match mouse.kind {
+            MouseEventKind::ScrollDown if in_list => self.move_down(1),
+            MouseEventKind::ScrollUp if in_list => self.move_up(1),
+            MouseEventKind::ScrollDown if in_preview => self.preview.scroll_down(1),
+            MouseEventKind::ScrollUp if in_preview => self.preview.scroll_up(1),
+            MouseEventKind::Down(MouseButton::Left)
+                if on_split && contains(self.area, mouse.column, mouse.row) =>
+            {
+                self.resizing = true
+            }
+            MouseEventKind::Down(MouseButton::Left) if in_list => {
+                if let Some(position) = self.position_at(mouse.row) {
+                    return Ok(self.click(position));
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.resizing && self.area.width > 0 => {
+                let column = mouse.column.saturating_sub(self.area.x) as u32;
+                let percent = column * 100 / self.area.width as u32;
+                self.split = (percent as u16).clamp(20, 80);
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.resizing = false,
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>, String> {
+        let half_page = (self.page_height / 2).max(1);
+        let page = self.page_height.max(1);
+
+        match action {
+            Action::SelectNext => self.move_down(1),
+            Action::SelectPrevious => self.move_up(1),
+            Action::SelectFirst => self.select(0),
+            Action::SelectLast => self.select(usize::MAX),
+            Action::SelectLine(line) => self.select(line.saturating_sub(1)),
+            Action::HalfPageDown => self.move_down(half_page),
+            Action::HalfPageUp => self.move_up(half_page),
+            Action::PageDown => self.move_down(page),
+            Action::PageUp => self.move_up(page),
+            Action::NextMatch => self.jump_to_match(true, false),
+            Action::PreviousMatch => self.jump_to_match(false, false),
+            Action::Filter => self.input = Some(Input::Filter),
+            Action::Search => {
+                self.search.clear();
+                self.input = Some(Input::Search);
+            }
+            Action::SetMark => self.input = Some(Input::SetMark),
+            Action::JumpToMark => self.input = Some(Input::JumpToMark),
+            Action::Apply => {
+                return Ok(self
+                    .selected_theme()
+                    .map(|theme| Action::ApplyTheme(theme.path.clone())))
+            }
+            Action::Random => return Ok(self.random()),
+            Action::ToggleLightDark => return Ok(self.toggle_light_dark()),
+            Action::OpenInEditor => {
+                return Ok(self
+                    .selected_theme()
+                    .map(|theme| Action::EditFile(theme.path.clone())))
+            }
+            Action::Refresh => self.preview.reload(),
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<(), String> {
         let layout = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints(vec![Constraint::Percentage(40), Constraint::Percentage(60)])
-            .split(canvas);
+            .constraints(vec![
+                Constraint::Percentage(self.split),
+                Constraint::Percentage(100 - self.split),
+            ])
+            .split(area);
 
         let left = layout[0];
         let right = layout[1];
@@ -36,20 +439,34 @@ impl ThemesTab {
         let theme_selection_filter = theme_selection[0];
         let theme_selection_list = theme_selection[1];
 
-        frame.render_widget(
-            Block::new()
-                .title("")
-                .title("🔍 (f) Filter")
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
+        let (input, input_style) = match self.input {
+            Some(Input::Search) => (format!("/{}", self.search), Style::new().yellow()),
+            Some(Input::Filter) => (self.filter.clone(), Style::new().yellow()),
+            _ => (self.filter.clone(), Style::new()),
+        };
+        f.render_widget(
+            Paragraph::new(input).block(
+                Block::new()
+                    .title("")
+                    .title(format!("🔍 ({}) Filter", self.filter_hint))
+                    .borders(Borders::ALL)
+                    .border_style(input_style)
+                    .border_type(BorderType::Rounded),
+            ),
             theme_selection_filter,
         );
 
-        // This should be stored outside of the function in your application state.
-        let mut state = ListState::default();
-        state.select(Some(self.counter as usize));
-
-        let items = (1..10).map(|i| format!("Theme #{}", i.to_string()));
+        let items = self.visible.iter().enumerate().map(|(position, &index)| {
+            let theme = &self.themes[index];
+            let line = match self.favorites.contains(&theme.name) {
+                true => Line::from(format!("★ {}", theme.name)),
+                false => Line::from(format!("  {}", theme.name)),
+            };
+            match self.is_match(position) {
+                true => ListItem::new(line.yellow().bold()),
+                false => ListItem::new(line),
+            }
+        });
         let list = List::new(items)
             .block(
                 Block::default()
@@ -62,35 +479,17 @@ impl ThemesTab {
             )
             .highlight_style(Style::new().bg(Color::Cyan).black())
             .repeat_highlight_symbol(true);
-        frame.render_stateful_widget(list, theme_selection_list, &mut state);
-        frame.render_widget(
-            Block::new()
-                .title("")
-                .title("👁️  Preview")
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-            right,
-        );
-    }
+        self.page_height = theme_selection_list.height.saturating_sub(2) as usize;
+        f.render_stateful_widget(list, theme_selection_list, &mut self.state);
 
-    fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            // it's important to check that the event is a key press event as
-            // crossterm also emits key release and repeat events on Windows.
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event)
-            }
-            _ => {}
-        };
+        let selected = self.selected_theme().map(|theme| theme.path.clone());
+        self.preview.show(selected.as_deref());
+        self.preview.draw(f, right)?;
 
-        Ok(())
-    }
+        self.area = area;
+        self.list_area = theme_selection_list;
+        self.preview_area = right;
 
-    fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match key_event.code {
-            KeyCode::Up => self.counter += 1,
-            KeyCode::Down => self.counter -= 1,
-            _ => {}
-        }
+        Ok(())
     }
 }
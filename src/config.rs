@@ -0,0 +1,243 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use toml_edit::{value, Array, DocumentMut, Item, TableLike};
+
+use crate::alacritty;
+use crate::export::{self, Exporter};
+use crate::hooks::{self, Hook};
+use crate::keymap::{Keymap, Scope, DEFAULT_PRESET};
+
+/// Settings of alacritty-skins itself, read from
+/// `$XDG_CONFIG_HOME/alacritty-skins/config.toml` (or `%APPDATA%` on Windows).
+///
+/// The file is edited with toml_edit so the comments and layout the user gave
+/// it survive when alacritty-skins writes back state such as favorites.
+#[derive(Debug)]
+pub struct Config {
+    pub path: Option<PathBuf>,
+    document: DocumentMut,
+}
+
+/// Directory holding the alacritty-skins configuration
+pub fn config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return env::var("APPDATA")
+            .ok()
+            .map(|appdata| Path::new(&appdata).join("alacritty-skins"));
+    }
+
+    let base = match env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => Path::new(&env::var("HOME").ok()?).join(".config"),
+    };
+
+    Some(base.join("alacritty-skins"))
+}
+
+/// Directory holding the user's own themes, listed along with the bundled ones
+pub fn themes_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("themes"))
+}
+
+impl Config {
+    /// Loads the configuration file, falling back to the defaults when it
+    /// does not exist
+    pub fn load() -> Result<Self, String> {
+        let path = config_dir().map(|dir| dir.join("config.toml"));
+
+        let document = match &path {
+            Some(path) if path.exists() => fs::read_to_string(path)
+                .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?
+                .parse::<DocumentMut>()
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?,
+            _ => DocumentMut::new(),
+        };
+
+        Ok(Self { path, document })
+    }
+
+    /// Builds the keymap described by the `[keymap]` section
+    pub fn keymap(&self) -> Result<Keymap, String> {
+        let Some(section) = self.document.get("keymap") else {
+            return Ok(Keymap::default());
+        };
+
+        let preset = match section.get("preset") {
+            Some(preset) => preset.as_str().ok_or("keymap.preset must be a string")?,
+            None => DEFAULT_PRESET,
+        };
+
+        let mut overrides = Vec::new();
+        for scope in [Scope::Global, Scope::Themes] {
+            let Some(table) = section.get(scope.name()) else {
+                continue;
+            };
+            let table = table
+                .as_table_like()
+                .ok_or_else(|| format!("keymap.{} must be a table", scope.name()))?;
+
+            for (chord, command) in table.iter() {
+                let command = command.as_str().ok_or_else(|| {
+                    format!(
+                        "[keymap.{}] `{}` must be bound to a command name",
+                        scope.name(),
+                        chord
+                    )
+                })?;
+                overrides.push((scope, chord.to_string(), command.to_string()));
+            }
+        }
+
+        Keymap::with_overrides(preset, &overrides)
+    }
+
+    /// Names of the themes marked as favorite
+    pub fn favorites(&self) -> Vec<String> {
+        self.document
+            .get("favorites")
+            .and_then(|favorites| favorites.as_array())
+            .map(|favorites| {
+                favorites
+                    .iter()
+                    .filter_map(|name| name.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Adds or removes a theme from the favorites and saves the file
+    pub fn toggle_favorite(&mut self, name: &str) -> Result<(), String> {
+        if self
+            .document
+            .get("favorites")
+            .and_then(|f| f.as_array())
+            .is_none()
+        {
+            self.document["favorites"] = value(Array::new());
+        }
+        let favorites = self.document["favorites"]
+            .as_array_mut()
+            .expect("favorites is an array");
+
+        let position = favorites
+            .iter()
+            .position(|item| item.as_str() == Some(name));
+        match position {
+            Some(index) => {
+                favorites.remove(index);
+            }
+            None => favorites.push(name),
+        }
+
+        self.save()
+    }
+
+    /// Whether themes are applied through the generated overlay file instead
+    /// of editing alacritty.toml
+    pub fn overlay(&self) -> bool {
+        self.document
+            .get("overlay")
+            .and_then(|overlay| overlay.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Whether changes to the Alacritty configuration are shown for review
+    /// before they are written
+    pub fn review(&self) -> bool {
+        self.document
+            .get("review")
+            .and_then(|review| review.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Files written from the applied theme, as set in the `[companions]`
+    /// section: each key names an export format and its value the path the
+    /// theme is exported to, relative to the configuration directory
+    pub fn companions(&self) -> Result<Vec<(&'static dyn Exporter, PathBuf)>, String> {
+        let Some(section) = self.document.get("companions") else {
+            return Ok(Vec::new());
+        };
+        let section = section
+            .as_table_like()
+            .ok_or("companions must be a table")?;
+        let dir = config_dir().unwrap_or_default();
+
+        section
+            .iter()
+            .map(|(format, path)| {
+                let path = path.as_str().ok_or_else(|| {
+                    format!("[companions] `{}` must be set to a file path", format)
+                })?;
+                let exporter =
+                    export::exporter(format).map_err(|e| format!("[companions] {}", e))?;
+                Ok((exporter, alacritty::resolve_import(path, &dir)))
+            })
+            .collect()
+    }
+
+    /// Commands run after a theme is applied, from the `[[hooks]]` tables
+    pub fn hooks(&self) -> Result<Vec<Hook>, String> {
+        let Some(section) = self.document.get("hooks") else {
+            return Ok(Vec::new());
+        };
+        let tables: Vec<&dyn TableLike> = match section {
+            Item::ArrayOfTables(tables) => tables.iter().map(|t| t as &dyn TableLike).collect(),
+            _ => section
+                .as_array()
+                .ok_or("hooks must be an array of tables")?
+                .iter()
+                .map(|hook| {
+                    hook.as_inline_table()
+                        .map(|t| t as &dyn TableLike)
+                        .ok_or("hooks must be an array of tables")
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        tables
+            .into_iter()
+            .map(|hook| {
+                let command = hook
+                    .get("command")
+                    .and_then(|command| command.as_str())
+                    .ok_or("Every hook needs a `command` string")?;
+                let timeout = match hook.get("timeout") {
+                    Some(timeout) => timeout
+                        .as_integer()
+                        .and_then(|seconds| u64::try_from(seconds).ok())
+                        .ok_or_else(|| {
+                            format!("The timeout of `{}` must be a number of seconds", command)
+                        })?,
+                    None => hooks::DEFAULT_TIMEOUT,
+                };
+
+                Ok(Hook {
+                    command: command.to_string(),
+                    timeout: Duration::from_secs(timeout),
+                })
+            })
+            .collect()
+    }
+
+    /// Turns the overlay mode on and saves the file
+    pub fn enable_overlay(&mut self) -> Result<(), String> {
+        self.document["overlay"] = value(true);
+        self.save()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Err("Could not determine where to store the configuration".to_string());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(path, self.document.to_string())
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e))
+    }
+}
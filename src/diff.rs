@@ -0,0 +1,192 @@
+use crossterm::style::Stylize;
+
+/// Lines of context kept around each change
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Keep,
+    Remove,
+    Add,
+}
+
+/// Renders the changes from `old` to `new` as a unified diff, the way
+/// `diff -u` does. Returns an empty string when both are the same.
+pub fn unified(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let edits = edits(&old, &new);
+    if edits.iter().all(|(edit, _, _)| *edit == Edit::Keep) {
+        return String::new();
+    }
+
+    let mut diff = format!("--- {}\n+++ {}\n", old_name, new_name);
+    let changed: Vec<usize> = (0..edits.len())
+        .filter(|index| edits[*index].0 != Edit::Keep)
+        .collect();
+
+    // Group the changes close enough to share their context into hunks
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in changed {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + CONTEXT + 1).min(edits.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        let hunk = &edits[start..end];
+        let (old_start, new_start) = (hunk[0].1, hunk[0].2);
+        let old_count = hunk
+            .iter()
+            .filter(|(edit, _, _)| *edit != Edit::Add)
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|(edit, _, _)| *edit != Edit::Remove)
+            .count();
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_count),
+            range(new_start, new_count)
+        ));
+
+        for (edit, old_index, new_index) in hunk {
+            let line = match edit {
+                Edit::Keep => format!(" {}", old[*old_index]),
+                Edit::Remove => format!("-{}", old[*old_index]),
+                Edit::Add => format!("+{}", new[*new_index]),
+            };
+            diff.push_str(&line);
+            diff.push('\n');
+        }
+    }
+
+    diff
+}
+
+/// Formats a hunk range, 1-based, as `diff -u` does for empty ranges too
+fn range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
+
+/// Shortest edit script between two lists of lines, from their longest common
+/// subsequence. Each edit carries the position it applies to in both lists.
+fn edits(old: &[&str], new: &[&str]) -> Vec<(Edit, usize, usize)> {
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = match old[i] == new[j] {
+                true => common[i + 1][j + 1] + 1,
+                false => common[i + 1][j].max(common[i][j + 1]),
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            edits.push((Edit::Keep, i, j));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            edits.push((Edit::Remove, i, j));
+            i += 1;
+        } else {
+            edits.push((Edit::Add, i, j));
+            j += 1;
+        }
+    }
+
+    edits
+}
+
+/// Colours a unified diff for the terminal: removed lines red, added lines
+/// green and hunk headers cyan
+pub fn colorize(diff: &str) -> String {
+    diff.lines()
+        .map(|line| match line {
+            _ if line.starts_with("---") || line.starts_with("+++") => line.bold().to_string(),
+            _ if line.starts_with("@@") => line.cyan().to_string(),
+            _ if line.starts_with('-') => line.red().to_string(),
+            _ if line.starts_with('+') => line.green().to_string(),
+            _ => line.to_string(),
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lines `1` to `n`, one per line
+    fn numbered(n: usize) -> String {
+        edited(n, &[])
+    }
+
+    /// Lines `1` to `n` with some of them replaced, or removed when empty
+    fn edited(n: usize, edits: &[(usize, &str)]) -> String {
+        (1..=n)
+            .map(
+                |line| match edits.iter().find(|(number, _)| *number == line) {
+                    Some((_, "")) => String::new(),
+                    Some((_, text)) => format!("{}\n", text),
+                    None => format!("{}\n", line),
+                },
+            )
+            .collect()
+    }
+
+    #[test]
+    fn same_text_has_no_diff() {
+        assert_eq!(unified("a\nb\n", "a\nb\n", "old", "new"), "");
+    }
+
+    #[test]
+    fn keeps_three_lines_of_context() {
+        let new = edited(10, &[(5, "five")]);
+
+        assert_eq!(
+            unified(&numbered(10), &new, "old", "new"),
+            "--- old\n+++ new\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n"
+        );
+    }
+
+    #[test]
+    fn splits_distant_changes_into_hunks() {
+        let new = edited(20, &[(2, "two"), (19, "nineteen")]);
+
+        assert_eq!(
+            unified(&numbered(20), &new, "old", "new"),
+            "--- old\n+++ new\n\
+             @@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n 4\n 5\n\
+             @@ -16,5 +16,5 @@\n 16\n 17\n 18\n-19\n+nineteen\n 20\n"
+        );
+    }
+
+    #[test]
+    fn merges_changes_sharing_context() {
+        let new = edited(10, &[(3, ""), (8, "eight")]);
+
+        assert_eq!(
+            unified(&numbered(10), &new, "old", "new"),
+            "--- old\n+++ new\n@@ -1,10 +1,9 @@\n 1\n 2\n-3\n 4\n 5\n 6\n 7\n-8\n+eight\n 9\n 10\n"
+        );
+    }
+
+    #[test]
+    fn created_file_starts_at_zero() {
+        assert_eq!(
+            unified("", "a\nb\n", "/dev/null", "new"),
+            "--- /dev/null\n+++ new\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::alacritty::AlacrittyTheme;
+use crate::config;
+use crate::theme::{Palette, Rgb};
+
+mod bat;
+mod foot;
+mod fzf;
+mod iterm;
+mod kitty;
+mod neovim;
+mod osc;
+mod tmux;
+mod wezterm;
+mod windows_terminal;
+mod xresources;
+
+/// Writes the colours of a theme in the format of another terminal or tool
+pub trait Exporter {
+    /// Name of the format, as passed to `--format`
+    fn name(&self) -> &'static str;
+
+    /// Extension of the files in the format, dot included
+    fn extension(&self) -> &'static str;
+
+    fn export(&self, name: &str, palette: &Palette) -> String;
+}
+
+/// Every built-in exporter
+pub const EXPORTERS: &[&dyn Exporter] = &[
+    &kitty::Kitty,
+    &foot::Foot,
+    &wezterm::WezTerm,
+    &windows_terminal::WindowsTerminal,
+    &xresources::Xresources,
+    &iterm::Iterm,
+    &osc::OscScript,
+    &tmux::Tmux,
+    &fzf::Fzf,
+    &bat::Bat,
+    &neovim::Neovim,
+];
+
+/// Names of the formats of every exporter
+pub fn names() -> Vec<&'static str> {
+    EXPORTERS.iter().map(|exporter| exporter.name()).collect()
+}
+
+/// Looks up an exporter by its format name
+pub fn exporter(format: &str) -> Result<&'static dyn Exporter, String> {
+    EXPORTERS
+        .iter()
+        .find(|exporter| exporter.name() == format)
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "Unknown format `{}`, expected one of {}",
+                format,
+                names().join(", ")
+            )
+        })
+}
+
+/// Writes the colours of a theme in the format of the exporter
+pub fn export(theme: &AlacrittyTheme, exporter: &dyn Exporter) -> Result<String, String> {
+    let palette = Palette::load(&theme.path)?;
+
+    Ok(exporter.export(&theme.name, &palette))
+}
+
+/// Exports a theme into the `exports` directory of the alacritty-skins
+/// configuration, replacing an earlier export of it
+pub fn export_to_file(theme: &AlacrittyTheme, exporter: &dyn Exporter) -> Result<PathBuf, String> {
+    let dir = config::config_dir()
+        .map(|dir| dir.join("exports"))
+        .ok_or_else(|| "Could not find the alacritty-skins configuration directory".to_string())?;
+    let path = dir.join(format!("{}{}", theme.name, exporter.extension()));
+
+    let contents = export(theme, exporter)?;
+    write(&path, &contents)?;
+
+    Ok(path)
+}
+
+/// Writes the companion files of the applied theme, going on past the ones
+/// that cannot be written
+pub fn write_companions(
+    theme: &AlacrittyTheme,
+    companions: &[(&'static dyn Exporter, PathBuf)],
+) -> Vec<Result<PathBuf, String>> {
+    let palette = match Palette::load(&theme.path) {
+        Ok(palette) => palette,
+        Err(error) => return vec![Err(error)],
+    };
+
+    companions
+        .iter()
+        .map(|(exporter, path)| {
+            write(path, &exporter.export(&theme.name, &palette))?;
+            Ok(path.clone())
+        })
+        .collect()
+}
+
+/// Writes a file, creating its directory first
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Unable to create {}: {}", dir.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Unable to write {}: {}", path.display(), e))
+}
+
+/// The sixteen ANSI colours in terminal order, normal then bright
+fn ansi(palette: &Palette) -> impl Iterator<Item = (usize, Rgb)> + '_ {
+    palette
+        .normal
+        .iter()
+        .chain(&palette.bright)
+        .enumerate()
+        .filter_map(|(index, color)| Some((index, (*color)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::import;
+
+    /// A palette setting every colour, each a different one
+    fn palette() -> Palette {
+        let color = |index: u8| Some(Rgb::new(index * 10, 255 - index * 10, index * 5));
+
+        Palette {
+            background: color(0),
+            foreground: color(1),
+            normal: std::array::from_fn(|index| color(2 + index as u8)),
+            bright: std::array::from_fn(|index| color(10 + index as u8)),
+            cursor: color(18),
+            cursor_text: color(19),
+            selection_background: color(20),
+            selection_text: color(21),
+        }
+    }
+
+    /// Exports the palette, reads the file back with the importer of the
+    /// format and returns the palette it found
+    fn round_trip(test: &str, format: &str, palette: &Palette) -> Palette {
+        let exporter = exporter(format).unwrap();
+        let dir = env::temp_dir().join(format!(
+            "alacritty-skins-export-{}-{}-{}",
+            test,
+            format,
+            std::process::id()
+        ));
+        let path = dir.join(format!("Test{}", exporter.extension()));
+        write(&path, &exporter.export("Test", palette)).unwrap();
+
+        let imported = import::read(&path, None).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(imported.themes.len(), 1);
+        assert!(imported.themes[0].unmapped.is_empty());
+        assert_eq!(imported.themes[0].name, "Test");
+
+        imported.themes[0].palette.clone()
+    }
+
+    #[test]
+    fn kitty_round_trip() {
+        assert_eq!(
+            round_trip("kitty_round_trip", "kitty", &palette()),
+            palette()
+        );
+    }
+
+    #[test]
+    fn foot_round_trip() {
+        assert_eq!(round_trip("foot_round_trip", "foot", &palette()), palette());
+    }
+
+    #[test]
+    fn iterm_round_trip() {
+        assert_eq!(
+            round_trip("iterm_round_trip", "iterm", &palette()),
+            palette()
+        );
+    }
+
+    #[test]
+    fn xresources_round_trip() {
+        // X resources have no cursor text colour
+        let expected = Palette {
+            cursor_text: None,
+            ..palette()
+        };
+
+        assert_eq!(
+            round_trip("xresources_round_trip", "xresources", &palette()),
+            expected
+        );
+    }
+
+    #[test]
+    fn windows_terminal_round_trip() {
+        // Windows Terminal schemes have no cursor and selection text colours
+        let expected = Palette {
+            cursor_text: None,
+            selection_text: None,
+            ..palette()
+        };
+
+        assert_eq!(
+            round_trip(
+                "windows_terminal_round_trip",
+                "windows-terminal",
+                &palette()
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn leaves_out_the_colours_the_palette_leaves_out() {
+        let palette = Palette {
+            cursor: None,
+            bright: [None; 8],
+            ..palette()
+        };
+
+        for format in ["kitty", "foot", "iterm", "xresources"] {
+            let expected = Palette {
+                // foot writes both cursor colours or neither, X resources
+                // have no cursor text colour
+                cursor_text: match format {
+                    "foot" | "xresources" => None,
+                    _ => palette.cursor_text,
+                },
+                ..palette.clone()
+            };
+            assert_eq!(
+                round_trip("missing_colours", format, &palette),
+                expected,
+                "{}",
+                format
+            );
+        }
+    }
+}
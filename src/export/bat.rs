@@ -0,0 +1,88 @@
+use plist::{Dictionary, Value};
+
+use super::Exporter;
+use crate::theme::{Palette, Rgb};
+
+/// A TextMate `.tmTheme` for bat, to put in `$(bat --config-dir)/themes`
+/// before running `bat cache --build`
+pub struct Bat;
+
+impl Exporter for Bat {
+    fn name(&self) -> &'static str {
+        "bat"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".tmTheme"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let [_, red, green, yellow, blue, magenta, cyan, _] = palette.normal;
+        let bright_black = palette.bright[0];
+
+        let mut settings = vec![Value::Dictionary(scope(
+            None,
+            &[
+                ("background", palette.background),
+                ("foreground", palette.foreground),
+                ("caret", palette.cursor.or(palette.foreground)),
+                ("selection", palette.selection_background.or(bright_black)),
+                ("gutterForeground", bright_black),
+            ],
+        ))];
+        let scopes = [
+            ("comment", bright_black),
+            ("string", green),
+            ("constant.numeric, constant.language", yellow),
+            ("constant.character.escape", cyan),
+            ("keyword, storage", magenta),
+            ("storage.type, support.type, entity.name.type", yellow),
+            ("entity.name.function, support.function", blue),
+            ("entity.name.tag", red),
+            ("entity.other.attribute-name", yellow),
+            ("variable.parameter", palette.foreground),
+            ("markup.heading", blue),
+            ("markup.inserted", green),
+            ("markup.deleted", red),
+            ("markup.changed", yellow),
+            ("invalid", red),
+        ];
+        for (selector, color) in scopes {
+            if color.is_some() {
+                settings.push(Value::Dictionary(scope(
+                    Some(selector),
+                    &[("foreground", color)],
+                )));
+            }
+        }
+
+        let mut theme = Dictionary::new();
+        theme.insert("name".to_string(), Value::from(name));
+        theme.insert("settings".to_string(), Value::Array(settings));
+
+        let mut xml = Vec::new();
+        match Value::Dictionary(theme).to_writer_xml(&mut xml) {
+            Ok(()) => String::from_utf8_lossy(&xml).to_string() + "\n",
+            Err(_) => String::new(),
+        }
+    }
+}
+
+/// A `settings` entry, global when no scope selector is given
+fn scope(selector: Option<&str>, colors: &[(&str, Option<Rgb>)]) -> Dictionary {
+    let mut settings = Dictionary::new();
+    for (key, color) in colors {
+        if let Some(color) = color {
+            settings.insert(key.to_string(), Value::from(color.to_hex()));
+        }
+    }
+
+    let mut entry = Dictionary::new();
+    if let Some(selector) = selector {
+        entry.insert("name".to_string(), Value::from(selector));
+        entry.insert("scope".to_string(), Value::from(selector));
+    }
+    entry.insert("settings".to_string(), Value::Dictionary(settings));
+
+    entry
+}
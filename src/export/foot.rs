@@ -0,0 +1,48 @@
+use super::Exporter;
+use crate::theme::{Palette, Rgb};
+
+/// The `[colors]` section of `foot.ini`, whose colours have no `#`
+pub struct Foot;
+
+impl Exporter for Foot {
+    fn name(&self) -> &'static str {
+        "foot"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".ini"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let hex = |color: Rgb| color.to_hex().trim_start_matches('#').to_string();
+        let mut lines = vec![
+            format!("# {}, exported by alacritty-skins", name),
+            "[colors]".to_string(),
+        ];
+
+        let keys = [
+            ("foreground", palette.foreground),
+            ("background", palette.background),
+            ("selection-foreground", palette.selection_text),
+            ("selection-background", palette.selection_background),
+        ];
+        for (key, color) in keys {
+            if let Some(color) = color {
+                lines.push(format!("{}={}", key, hex(color)));
+            }
+        }
+        // foot sets both cursor colours at once, the text colour first
+        if let (Some(text), Some(cursor)) = (palette.cursor_text, palette.cursor) {
+            lines.push(format!("cursor={} {}", hex(text), hex(cursor)));
+        }
+        for (prefix, colors) in [("regular", &palette.normal), ("bright", &palette.bright)] {
+            for (index, color) in colors.iter().enumerate() {
+                if let Some(color) = color {
+                    lines.push(format!("{}{}={}", prefix, index, hex(*color)));
+                }
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
@@ -0,0 +1,50 @@
+use super::Exporter;
+use crate::theme::Palette;
+
+/// A shell snippet adding the colours to `FZF_DEFAULT_OPTS`, to source from
+/// the shell startup file
+pub struct Fzf;
+
+impl Exporter for Fzf {
+    fn name(&self) -> &'static str {
+        "fzf"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".fzf.sh"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let [_, red, green, yellow, blue, magenta, cyan, _] = palette.normal;
+        let bright_black = palette.bright[0];
+        let colors = [
+            ("bg", palette.background),
+            ("fg", palette.foreground),
+            ("bg+", palette.selection_background.or(bright_black)),
+            ("fg+", palette.selection_text.or(palette.foreground)),
+            ("hl", blue),
+            ("hl+", blue),
+            ("info", yellow),
+            ("prompt", cyan),
+            ("pointer", magenta),
+            ("marker", green),
+            ("spinner", magenta),
+            ("header", cyan),
+            ("border", bright_black),
+            ("gutter", palette.background),
+            ("query", palette.foreground),
+            ("disabled", bright_black),
+            ("label", red),
+        ];
+        let colors: Vec<String> = colors
+            .into_iter()
+            .filter_map(|(key, color)| Some(format!("{}:{}", key, color?.to_hex())))
+            .collect();
+
+        format!(
+            "# {}, exported by alacritty-skins\nexport FZF_DEFAULT_OPTS=\"$FZF_DEFAULT_OPTS --color={}\"\n",
+            name,
+            colors.join(",")
+        )
+    }
+}
@@ -0,0 +1,59 @@
+use plist::{Dictionary, Value};
+
+use super::{ansi, Exporter};
+use crate::theme::{Palette, Rgb};
+
+/// An iTerm2 `.itermcolors` property list
+pub struct Iterm;
+
+impl Exporter for Iterm {
+    fn name(&self) -> &'static str {
+        "iterm"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".itermcolors"
+    }
+
+    fn export(&self, _name: &str, palette: &Palette) -> String {
+        let mut colors = Dictionary::new();
+        for (index, color) in ansi(palette) {
+            colors.insert(format!("Ansi {} Color", index), to_dictionary(color));
+        }
+        let keys = [
+            ("Background", palette.background),
+            ("Foreground", palette.foreground),
+            ("Cursor", palette.cursor),
+            ("Cursor Text", palette.cursor_text),
+            ("Selection", palette.selection_background),
+            ("Selected Text", palette.selection_text),
+        ];
+        for (key, color) in keys {
+            if let Some(color) = color {
+                colors.insert(format!("{} Color", key), to_dictionary(color));
+            }
+        }
+
+        let mut xml = Vec::new();
+        match Value::Dictionary(colors).to_writer_xml(&mut xml) {
+            Ok(()) => String::from_utf8_lossy(&xml).to_string() + "\n",
+            Err(_) => String::new(),
+        }
+    }
+}
+
+/// A colour as iTerm2 stores it, sRGB components between 0 and 1
+fn to_dictionary(color: Rgb) -> Value {
+    let mut dictionary = Dictionary::new();
+    let components = [("Red", color.r), ("Green", color.g), ("Blue", color.b)];
+    for (name, component) in components {
+        dictionary.insert(
+            format!("{} Component", name),
+            Value::Real(component as f64 / 255.0),
+        );
+    }
+    dictionary.insert("Alpha Component".to_string(), Value::Real(1.0));
+    dictionary.insert("Color Space".to_string(), Value::from("sRGB"));
+
+    Value::Dictionary(dictionary)
+}
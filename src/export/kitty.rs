@@ -0,0 +1,37 @@
+use super::{ansi, Exporter};
+use crate::theme::Palette;
+
+/// kitty configuration, to `include` from `kitty.conf`
+pub struct Kitty;
+
+impl Exporter for Kitty {
+    fn name(&self) -> &'static str {
+        "kitty"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".conf"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let mut lines = vec![format!("# {}, exported by alacritty-skins", name)];
+        let keys = [
+            ("foreground", palette.foreground),
+            ("background", palette.background),
+            ("selection_foreground", palette.selection_text),
+            ("selection_background", palette.selection_background),
+            ("cursor", palette.cursor),
+            ("cursor_text_color", palette.cursor_text),
+        ];
+        for (key, color) in keys {
+            if let Some(color) = color {
+                lines.push(format!("{} {}", key, color.to_hex()));
+            }
+        }
+        for (index, color) in ansi(palette) {
+            lines.push(format!("color{} {}", index, color.to_hex()));
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
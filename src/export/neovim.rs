@@ -0,0 +1,29 @@
+use super::{ansi, Exporter};
+use crate::theme::Palette;
+
+/// Lua setting the `g:terminal_color_N` colours of the Neovim terminal, to
+/// `require` or `dofile` from `init.lua`
+pub struct Neovim;
+
+impl Exporter for Neovim {
+    fn name(&self) -> &'static str {
+        "neovim"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".lua"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let mut lines = vec![format!("-- {}, exported by alacritty-skins", name)];
+        for (index, color) in ansi(palette) {
+            lines.push(format!(
+                "vim.g.terminal_color_{} = \"{}\"",
+                index,
+                color.to_hex()
+            ));
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
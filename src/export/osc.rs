@@ -0,0 +1,179 @@
+use super::{ansi, Exporter};
+use crate::theme::{Palette, Rgb};
+
+/// The script around the sequences: `osc` wraps a sequence for the terminal
+/// outside tmux or GNU screen, which otherwise keep it to themselves
+const SCRIPT: &str = r#"#!/bin/sh
+# {name}, exported by alacritty-skins
+#
+# Run it to set the colours of the terminal it runs in, or with --reset to
+# bring back the terminal's own colours. Inside tmux (3.3 and later need
+# `set -g allow-passthrough on`) and GNU screen the sequences are passed
+# through to the outer terminal.
+#
+# As a one-liner, outside tmux and screen:
+# printf '{one_liner}'
+
+osc() {
+	if [ -n "$TMUX" ]; then
+		printf '\033Ptmux;\033\033]%s\007\033\\' "$1"
+	elif [ -n "$STY" ]; then
+		printf '\033P\033]%s\007\033\\' "$1"
+	else
+		printf '\033]%s\007' "$1"
+	fi
+}
+
+if [ "$1" = --reset ]; then
+	osc 104
+	osc 110
+	osc 111
+	osc 112
+	exit
+fi
+
+"#;
+
+/// A POSIX shell script setting the colours of the terminal it runs in
+/// through OSC escape sequences, for SSH sessions and other terminals whose
+/// configuration cannot be changed. `--reset` brings back the terminal's own
+/// colours.
+pub struct OscScript;
+
+impl Exporter for OscScript {
+    fn name(&self) -> &'static str {
+        "osc-sh"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".sh"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let sequences = sequences(palette);
+        let one_liner: String = sequences
+            .iter()
+            .map(|sequence| format!("\\033]{}\\007", sequence))
+            .collect();
+
+        let mut script = SCRIPT
+            .replace("{name}", name)
+            .replace("{one_liner}", &one_liner);
+        for sequence in &sequences {
+            script.push_str(&format!("osc '{}'\n", sequence));
+        }
+
+        script
+    }
+}
+
+/// The OSC sequences, without their introducer and terminator, setting the
+/// sixteen colours then the foreground, background and cursor
+fn sequences(palette: &Palette) -> Vec<String> {
+    let mut sequences: Vec<String> = ansi(palette)
+        .map(|(index, color)| format!("4;{};{}", index, x11(color)))
+        .collect();
+    let dynamic = [
+        (10, palette.foreground),
+        (11, palette.background),
+        (12, palette.cursor),
+    ];
+    for (code, color) in dynamic {
+        if let Some(color) = color {
+            sequences.push(format!("{};{}", code, x11(color)));
+        }
+    }
+
+    sequences
+}
+
+/// The `rgb:rr/gg/bb` notation of XParseColor, understood by more terminals
+/// than `#rrggbb`
+fn x11(color: Rgb) -> String {
+    format!("rgb:{:02x}/{:02x}/{:02x}", color.r, color.g, color.b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> Palette {
+        let mut palette = Palette {
+            background: Some(Rgb::new(0x28, 0x28, 0x28)),
+            foreground: Some(Rgb::new(0xeb, 0xdb, 0xb2)),
+            ..Palette::default()
+        };
+        palette.normal[1] = Some(Rgb::new(0xcc, 0x24, 0x1d));
+        palette.bright[1] = Some(Rgb::new(0xfb, 0x49, 0x34));
+
+        palette
+    }
+
+    #[test]
+    fn sets_the_colours_the_palette_sets() {
+        // No cursor colour and only two of the sixteen
+        assert_eq!(
+            sequences(&palette()),
+            [
+                "4;1;rgb:cc/24/1d",
+                "4;9;rgb:fb/49/34",
+                "10;rgb:eb/db/b2",
+                "11;rgb:28/28/28",
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_the_sequences_and_the_one_liner() {
+        let script = OscScript.export("Gruvbox", &palette());
+
+        assert!(script.starts_with("#!/bin/sh\n# Gruvbox, exported by alacritty-skins\n"));
+        assert!(script.contains(
+            "# printf '\\033]4;1;rgb:cc/24/1d\\007\\033]4;9;rgb:fb/49/34\\007\
+             \\033]10;rgb:eb/db/b2\\007\\033]11;rgb:28/28/28\\007'\n"
+        ));
+        assert!(script.ends_with(
+            "osc '4;1;rgb:cc/24/1d'\nosc '4;9;rgb:fb/49/34'\n\
+             osc '10;rgb:eb/db/b2'\nosc '11;rgb:28/28/28'\n"
+        ));
+        assert!(!script.contains("12;"));
+    }
+
+    /// What the script prints when run by `sh` with the given environment
+    #[cfg(unix)]
+    fn run(args: &[&str], env: &[(&str, &str)]) -> String {
+        let script = OscScript.export("Gruvbox", &palette());
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&script)
+            .arg("sh")
+            .args(args)
+            .env_remove("TMUX")
+            .env_remove("STY")
+            .envs(env.iter().copied())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wraps_the_sequences_for_tmux_and_screen() {
+        assert!(run(&[], &[]).starts_with("\x1b]4;1;rgb:cc/24/1d\x07\x1b]4;9;"));
+        assert!(run(&[], &[("TMUX", "/tmp/tmux-0/default,1,0")])
+            .starts_with("\x1bPtmux;\x1b\x1b]4;1;rgb:cc/24/1d\x07\x1b\\\x1bPtmux;"));
+        assert!(run(&[], &[("STY", "1.pts-0.host")])
+            .starts_with("\x1bP\x1b]4;1;rgb:cc/24/1d\x07\x1b\\\x1bP"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resets_the_colours() {
+        assert_eq!(
+            run(&["--reset"], &[]),
+            "\x1b]104\x07\x1b]110\x07\x1b]111\x07\x1b]112\x07"
+        );
+    }
+}
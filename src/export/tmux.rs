@@ -0,0 +1,44 @@
+use super::Exporter;
+use crate::theme::Palette;
+
+/// tmux `set -g` lines colouring the status bar, borders and copy mode, to
+/// `source-file` from `tmux.conf`
+pub struct Tmux;
+
+impl Exporter for Tmux {
+    fn name(&self) -> &'static str {
+        "tmux"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".tmux.conf"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let [_, red, _, yellow, blue, ..] = palette.normal;
+        let bright_black = palette.bright[0];
+        let selection = palette.selection_background.or(bright_black);
+        let options = [
+            ("status-style", palette.background, palette.foreground),
+            ("message-style", palette.background, yellow),
+            ("window-status-current-style", blue, palette.background),
+            ("window-status-activity-style", palette.background, red),
+            ("pane-border-style", None, bright_black),
+            ("pane-active-border-style", None, blue),
+            ("mode-style", selection, palette.selection_text),
+        ];
+
+        let mut lines = vec![format!("# {}, exported by alacritty-skins", name)];
+        for (option, bg, fg) in options {
+            let style: Vec<String> = [("bg", bg), ("fg", fg)]
+                .into_iter()
+                .filter_map(|(key, color)| Some(format!("{}={}", key, color?.to_hex())))
+                .collect();
+            if !style.is_empty() {
+                lines.push(format!("set -g {} \"{}\"", option, style.join(",")));
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
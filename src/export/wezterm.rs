@@ -0,0 +1,52 @@
+use toml_edit::{value, Array, DocumentMut, Item, Table};
+
+use super::Exporter;
+use crate::theme::{Palette, Rgb};
+
+/// A WezTerm colour scheme, for a `colors` directory of its configuration
+pub struct WezTerm;
+
+impl Exporter for WezTerm {
+    fn name(&self) -> &'static str {
+        "wezterm"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".toml"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let mut colors = Table::new();
+        let keys = [
+            ("foreground", palette.foreground),
+            ("background", palette.background),
+            ("cursor_bg", palette.cursor),
+            ("cursor_border", palette.cursor),
+            ("cursor_fg", palette.cursor_text),
+            ("selection_bg", palette.selection_background),
+            ("selection_fg", palette.selection_text),
+        ];
+        for (key, color) in keys {
+            if let Some(color) = color {
+                colors.insert(key, value(color.to_hex()));
+            }
+        }
+        // WezTerm only takes complete lists of eight colours
+        for (key, ansi) in [("ansi", &palette.normal), ("brights", &palette.bright)] {
+            let ansi: Option<Vec<Rgb>> = ansi.iter().copied().collect();
+            if let Some(ansi) = ansi {
+                let ansi: Array = ansi.iter().map(|color| color.to_hex()).collect();
+                colors.insert(key, value(ansi));
+            }
+        }
+
+        let mut metadata = Table::new();
+        metadata.insert("name", value(name));
+        metadata.insert("origin_url", value("alacritty-skins"));
+
+        let mut document = DocumentMut::new();
+        document.insert("colors", Item::Table(colors));
+        document.insert("metadata", Item::Table(metadata));
+        document.to_string()
+    }
+}
@@ -0,0 +1,44 @@
+use serde_json::{Map, Value};
+
+use super::Exporter;
+use crate::import::windows_terminal::{bright_keys, ANSI_KEYS};
+use crate::theme::Palette;
+
+/// A scheme to paste into the `schemes` of Windows Terminal's
+/// `settings.json`
+pub struct WindowsTerminal;
+
+impl Exporter for WindowsTerminal {
+    fn name(&self) -> &'static str {
+        "windows-terminal"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".json"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let mut scheme = Map::new();
+        scheme.insert("name".to_string(), Value::from(name));
+
+        let keys = [
+            ("background".to_string(), palette.background),
+            ("foreground".to_string(), palette.foreground),
+            ("cursorColor".to_string(), palette.cursor),
+            (
+                "selectionBackground".to_string(),
+                palette.selection_background,
+            ),
+        ]
+        .into_iter()
+        .chain(ANSI_KEYS.map(String::from).into_iter().zip(palette.normal))
+        .chain(bright_keys().into_iter().zip(palette.bright));
+        for (key, color) in keys {
+            if let Some(color) = color {
+                scheme.insert(key, Value::from(color.to_hex().to_uppercase()));
+            }
+        }
+
+        serde_json::to_string_pretty(&scheme).unwrap_or_default() + "\n"
+    }
+}
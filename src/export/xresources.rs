@@ -0,0 +1,36 @@
+use super::{ansi, Exporter};
+use crate::theme::Palette;
+
+/// X resources, to merge with `xrdb -merge`
+pub struct Xresources;
+
+impl Exporter for Xresources {
+    fn name(&self) -> &'static str {
+        "xresources"
+    }
+
+    fn extension(&self) -> &'static str {
+        ".Xresources"
+    }
+
+    fn export(&self, name: &str, palette: &Palette) -> String {
+        let mut lines = vec![format!("! {}, exported by alacritty-skins", name)];
+        let keys = [
+            ("foreground", palette.foreground),
+            ("background", palette.background),
+            ("cursorColor", palette.cursor),
+            ("highlightColor", palette.selection_background),
+            ("highlightTextColor", palette.selection_text),
+        ];
+        for (key, color) in keys {
+            if let Some(color) = color {
+                lines.push(format!("*.{}: {}", key, color.to_hex()));
+            }
+        }
+        for (index, color) in ansi(palette) {
+            lines.push(format!("*.color{}: {}", index, color.to_hex()));
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
@@ -0,0 +1,37 @@
+/// Scores how well `pattern` fuzzy matches `candidate`, ignoring case.
+///
+/// Every character of the pattern has to appear in the candidate in the same
+/// order, otherwise there is no match. Runs of consecutive characters and
+/// characters starting a word score higher, so `gd` prefers `Gruvbox-Dark`
+/// over `Digerati`.
+pub fn score(pattern: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for wanted in pattern.chars().filter(|c| !c.is_whitespace()) {
+        let wanted = wanted.to_lowercase().next()?;
+        let offset = candidate[position..]
+            .iter()
+            .position(|c| c.to_lowercase().next() == Some(wanted))?;
+        let index = position + offset;
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 5;
+        }
+        let word_start = index == 0
+            || matches!(candidate[index - 1], '-' | '_' | ' ' | '.')
+            || (candidate[index].is_uppercase() && candidate[index - 1].is_lowercase());
+        if word_start {
+            score += 8;
+        }
+
+        previous_match = Some(index);
+        position = index + 1;
+    }
+
+    // Between equally good matches, prefer the shorter candidate
+    Some(score * 100 - candidate.len() as i64)
+}
@@ -0,0 +1,287 @@
+use std::{fs, path::Path};
+
+use crate::alacritty::AlacrittyTheme;
+use crate::theme::{Palette, Rgb, SAMPLE};
+
+/// Size of a character cell of the mock terminal, in pixels
+const CELL_WIDTH: usize = 9;
+const LINE_HEIGHT: usize = 20;
+/// Columns of the mock terminal
+const COLUMNS: usize = 46;
+
+/// Colours drawn when a theme leaves them out, those of the Alacritty default
+const DEFAULT_BACKGROUND: Rgb = Rgb {
+    r: 0x18,
+    g: 0x18,
+    b: 0x18,
+};
+const DEFAULT_FOREGROUND: Rgb = Rgb {
+    r: 0xd8,
+    g: 0xd8,
+    b: 0xd8,
+};
+
+/// What `generate` wrote
+#[derive(Debug, Default)]
+pub struct Gallery {
+    pub count: usize,
+    /// Themes left out because they could not be read
+    pub warnings: Vec<String>,
+}
+
+/// Writes an SVG mock-up of every theme into `dir`, along with an
+/// `index.html` listing them with their contrast. Nothing is fetched from the
+/// network, the directory can be browsed offline.
+pub fn generate(themes: &[AlacrittyTheme], dir: &Path) -> Result<Gallery, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Unable to create {}: {}", dir.display(), e))?;
+
+    let mut gallery = Gallery::default();
+    let mut cards = Vec::new();
+    let mut file_names = Vec::new();
+    for theme in themes {
+        let palette = match Palette::load(&theme.path) {
+            Ok(palette) => palette,
+            Err(error) => {
+                gallery.warnings.push(error);
+                continue;
+            }
+        };
+
+        let file_name = unique_file_name(&theme.name, &mut file_names);
+        let path = dir.join(&file_name);
+        fs::write(&path, svg(&theme.name, &palette))
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+        cards.push(card(&theme.name, &file_name, &palette));
+        gallery.count += 1;
+    }
+
+    let path = dir.join("index.html");
+    fs::write(&path, INDEX.replace("{cards}", &cards.join("\n")))
+        .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+
+    Ok(gallery)
+}
+
+/// The SVG file of a theme, numbered when a theme of the same name, such as a
+/// user theme shadowing a bundled one, already has the plain name. Names are
+/// compared ignoring case, as on macOS and Windows file systems.
+fn unique_file_name(name: &str, taken: &mut Vec<String>) -> String {
+    let mut file_name = format!("{}.svg", name);
+    let mut number = 1;
+    while taken.contains(&file_name.to_lowercase()) {
+        number += 1;
+        file_name = format!("{}-{}.svg", name, number);
+    }
+    taken.push(file_name.to_lowercase());
+
+    file_name
+}
+
+/// A mock terminal showing the sample text and the swatches of the sixteen
+/// colours
+fn svg(name: &str, palette: &Palette) -> String {
+    let background = palette.background.unwrap_or(DEFAULT_BACKGROUND);
+    let foreground = palette.foreground.unwrap_or(DEFAULT_FOREGROUND);
+    let width = COLUMNS * CELL_WIDTH + 2 * CELL_WIDTH;
+    // The sample, a blank line and two rows of swatches, with a line of
+    // padding above and below
+    let height = (SAMPLE.len() + 5) * LINE_HEIGHT;
+
+    let mut svg = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        ),
+        format!("<title>{}</title>", escape(name)),
+        format!(
+            r#"<rect width="{}" height="{}" rx="8" fill="{}"/>"#,
+            width,
+            height,
+            background.to_hex()
+        ),
+        format!(
+            r#"<g font-family="ui-monospace, Menlo, Consolas, monospace" font-size="14" fill="{}" xml:space="preserve">"#,
+            foreground.to_hex()
+        ),
+    ];
+
+    for (row, spans) in SAMPLE.iter().enumerate() {
+        let tspans: String = spans
+            .iter()
+            .map(
+                |(text, index)| match index.and_then(|index| palette.normal[index]) {
+                    Some(color) => format!(
+                        r#"<tspan fill="{}">{}</tspan>"#,
+                        color.to_hex(),
+                        escape(text)
+                    ),
+                    None => escape(text),
+                },
+            )
+            .collect();
+        svg.push(format!(
+            r#"<text x="{}" y="{}">{}</text>"#,
+            CELL_WIDTH,
+            (row + 2) * LINE_HEIGHT - 5,
+            tspans
+        ));
+    }
+    svg.push("</g>".to_string());
+
+    for (row, colors) in [&palette.normal, &palette.bright].into_iter().enumerate() {
+        let y = (SAMPLE.len() + 2 + row) * LINE_HEIGHT + 3;
+        for (column, color) in colors.iter().enumerate() {
+            let Some(color) = color else {
+                continue;
+            };
+            svg.push(format!(
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                CELL_WIDTH * (1 + column * 5),
+                y,
+                CELL_WIDTH * 4,
+                LINE_HEIGHT - 6,
+                color.to_hex()
+            ));
+        }
+    }
+    svg.push("</svg>".to_string());
+
+    svg.join("\n") + "\n"
+}
+
+/// The entry of a theme in the index
+fn card(name: &str, file_name: &str, palette: &Palette) -> String {
+    let score = |contrast: Option<f64>| match contrast {
+        Some(contrast) => format!("{:.1}:1", contrast),
+        None => "n/a".to_string(),
+    };
+    // The WCAG AA level for normal text
+    let grade = |contrast: Option<f64>| match contrast {
+        Some(contrast) if contrast >= 4.5 => "pass",
+        _ => "fail",
+    };
+
+    format!(
+        r#"<figure class="{variant}">
+<img src="{src}" alt="{name}" loading="lazy">
+<figcaption><strong>{name}</strong> <span class="variant">{variant}</span><br>
+text <span class="{text_grade}">{text}</span> · ANSI min <span class="{ansi_grade}">{ansi}</span></figcaption>
+</figure>"#,
+        variant = palette.variant(),
+        src = escape(
+            &file_name
+                .replace('%', "%25")
+                .replace('#', "%23")
+                .replace(' ', "%20")
+        ),
+        name = escape(name),
+        text = score(palette.contrast()),
+        text_grade = grade(palette.contrast()),
+        ansi = score(palette.min_ansi_contrast()),
+        ansi_grade = grade(palette.min_ansi_contrast()),
+    )
+}
+
+/// Escapes text for HTML and SVG
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const INDEX: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>alacritty-skins gallery</title>
+<style>
+body { font-family: system-ui, sans-serif; margin: 2em; background: #f4f4f4; color: #222; }
+nav button { font: inherit; padding: 0.3em 1em; }
+nav button.active { font-weight: bold; }
+main { display: flex; flex-wrap: wrap; gap: 1.5em; margin-top: 1.5em; }
+figure { margin: 0; }
+figcaption { margin-top: 0.4em; font-size: 0.9em; }
+.variant { color: #777; }
+.pass { color: #2e7d32; }
+.fail { color: #c62828; }
+.hidden { display: none; }
+</style>
+</head>
+<body>
+<h1>alacritty-skins gallery</h1>
+<p>Contrast is the WCAG ratio with the background, of the foreground and of the
+least readable ANSI colour; 4.5:1 is the AA level for text.</p>
+<nav>
+<button class="active" data-show="all">All</button>
+<button data-show="light">Light</button>
+<button data-show="dark">Dark</button>
+</nav>
+<main>
+{cards}
+</main>
+<script>
+for (const button of document.querySelectorAll("nav button")) {
+  button.addEventListener("click", () => {
+    const show = button.dataset.show;
+    for (const other of document.querySelectorAll("nav button")) {
+      other.classList.toggle("active", other === button);
+    }
+    for (const figure of document.querySelectorAll("figure")) {
+      figure.classList.toggle("hidden", show !== "all" && !figure.classList.contains(show));
+    }
+  });
+}
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn numbers_repeated_names() {
+        let mut taken = Vec::new();
+        let names: Vec<String> = ["Nord", "nord", "Nord-2", "Dracula", "Nord"]
+            .iter()
+            .map(|name| unique_file_name(name, &mut taken))
+            .collect();
+
+        assert_eq!(
+            names,
+            [
+                "Nord.svg",
+                "nord-2.svg",
+                "Nord-2-2.svg",
+                "Dracula.svg",
+                "Nord-3.svg"
+            ]
+        );
+    }
+
+    #[test]
+    fn themes_of_the_same_name_get_their_own_image() {
+        let dir = env::temp_dir().join(format!("alacritty-skins-gallery-{}", std::process::id()));
+        let themes: Vec<AlacrittyTheme> = ["bundled", "user"]
+            .iter()
+            .map(|source| {
+                let path = dir.join(source).join("Nord.toml");
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, "[colors.primary]\nbackground = \"#2e3440\"\n").unwrap();
+                AlacrittyTheme::new(path)
+            })
+            .collect();
+
+        let out = dir.join("gallery");
+        let gallery = generate(&themes, &out).unwrap();
+        let index = fs::read_to_string(out.join("index.html")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(gallery.count, 2);
+        assert!(index.contains(r#"src="Nord.svg""#));
+        assert!(index.contains(r#"src="Nord-2.svg""#));
+    }
+}
@@ -0,0 +1,243 @@
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::alacritty::AlacrittyTheme;
+use crate::theme::Palette;
+
+/// Seconds a hook may run when its `timeout` is not set
+pub const DEFAULT_TIMEOUT: u64 = 10;
+
+/// How long the output of a hook is waited for once it has exited. Processes
+/// it started in the background, as with `polybar &`, hold on to its pipes
+/// and what they print later is not waited for.
+const OUTPUT_GRACE: Duration = Duration::from_millis(200);
+
+/// A command run by the shell after a theme is applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hook {
+    pub command: String,
+    pub timeout: Duration,
+}
+
+/// Runs the hooks one after the other, with the applied theme described in
+/// `ALACRITTY_SKINS_THEME`, `ALACRITTY_SKINS_THEME_PATH` and
+/// `ALACRITTY_SKINS_VARIANT` (`light` or `dark`).
+///
+/// Each result is a message for the log: the command and what it printed, or
+/// why it failed. A failing hook does not stop the ones after it.
+pub fn run(hooks: &[Hook], theme: &AlacrittyTheme) -> Vec<Result<String, String>> {
+    if hooks.is_empty() {
+        return Vec::new();
+    }
+
+    let variant = Palette::load(&theme.path)
+        .map(|palette| palette.variant())
+        .unwrap_or("dark");
+    let env = [
+        ("ALACRITTY_SKINS_THEME", theme.name.clone()),
+        (
+            "ALACRITTY_SKINS_THEME_PATH",
+            theme.path.display().to_string(),
+        ),
+        ("ALACRITTY_SKINS_VARIANT", variant.to_string()),
+    ];
+
+    hooks.iter().map(|hook| run_hook(hook, &env)).collect()
+}
+
+fn run_hook(hook: &Hook, env: &[(&str, String)]) -> Result<String, String> {
+    let mut child = shell(&hook.command)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Unable to run `{}`: {}", hook.command, e))?;
+
+    // Read the output as it comes so a chatty hook does not fill the pipes
+    let readers = [
+        child.stdout.take().map(read_chunks),
+        child.stderr.take().map(read_chunks),
+    ];
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= hook.timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "`{}` timed out after {}s",
+                    hook.command,
+                    hook.timeout.as_secs()
+                ));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(format!("Unable to run `{}`: {}", hook.command, e)),
+        }
+    };
+
+    let deadline = Instant::now() + OUTPUT_GRACE;
+    let output: Vec<String> = readers
+        .iter()
+        .flatten()
+        .map(|reader| collect(reader, deadline))
+        .map(|output| output.trim_end().to_string())
+        .filter(|output| !output.is_empty())
+        .collect();
+    let mut message = match status.success() {
+        true => format!("Ran `{}`", hook.command),
+        false => format!("`{}` failed with {}", hook.command, status),
+    };
+    for output in output {
+        message.push('\n');
+        message.push_str(&output);
+    }
+
+    match status.success() {
+        true => Ok(message),
+        false => Err(message),
+    }
+}
+
+/// The command line run by the platform's shell
+fn shell(command: &str) -> Command {
+    let (program, flag) = match cfg!(windows) {
+        true => ("cmd", "/C"),
+        false => ("sh", "-c"),
+    };
+    let mut shell = Command::new(program);
+    shell.arg(flag).arg(command);
+
+    shell
+}
+
+/// Reads a pipe on a thread of its own, sending what it reads until the pipe
+/// is closed. Once nobody listens the pipe is still drained, so background
+/// processes the hook started do not fail writing to it.
+fn read_chunks(mut pipe: impl Read + Send + 'static) -> Receiver<Vec<u8>> {
+    let (chunks_tx, chunks_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buffer = [0; 4096];
+        let mut listening = true;
+        loop {
+            match pipe.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(read) if listening => {
+                    listening = chunks_tx.send(buffer[..read].to_vec()).is_ok();
+                }
+                Ok(_) => {}
+            }
+        }
+    });
+
+    chunks_rx
+}
+
+/// What a reader sent until its pipe was closed, or until the deadline
+fn collect(reader: &Receiver<Vec<u8>>, deadline: Instant) -> String {
+    let mut output = Vec::new();
+    while let Ok(chunk) = reader.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        output.extend(chunk);
+    }
+
+    String::from_utf8_lossy(&output).to_string()
+}
+
+// The hooks of the tests are `sh` command lines
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    fn hook(command: &str, timeout: u64) -> Hook {
+        Hook {
+            command: command.to_string(),
+            timeout: Duration::from_secs(timeout),
+        }
+    }
+
+    /// A light theme written in a directory of the test
+    fn theme(test: &str) -> AlacrittyTheme {
+        let dir = env::temp_dir().join(format!(
+            "alacritty-skins-hooks-{}-{}",
+            test,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Paper.toml");
+        fs::write(&path, "[colors.primary]\nbackground = \"#f8f8f8\"\n").unwrap();
+
+        AlacrittyTheme::new(path)
+    }
+
+    #[test]
+    fn describes_the_theme_in_the_environment() {
+        let theme = theme("env");
+        let command =
+            r#"echo "$ALACRITTY_SKINS_THEME $ALACRITTY_SKINS_VARIANT $ALACRITTY_SKINS_THEME_PATH""#;
+
+        let results = run(&[hook(command, 10)], &theme);
+
+        assert_eq!(
+            results,
+            [Ok(format!(
+                "Ran `{}`\nPaper light {}",
+                command,
+                theme.path.display()
+            ))]
+        );
+    }
+
+    #[test]
+    fn reports_failures_and_runs_the_next_hooks() {
+        let theme = theme("failures");
+
+        let results = run(
+            &[
+                hook("echo partial; echo oops >&2; exit 3", 10),
+                hook("true", 10),
+            ],
+            &theme,
+        );
+
+        assert_eq!(
+            results,
+            [
+                Err("`echo partial; echo oops >&2; exit 3` failed with exit status: 3\npartial\noops"
+                    .to_string()),
+                Ok("Ran `true`".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn kills_hooks_running_past_their_timeout() {
+        let theme = theme("timeout");
+        let started = Instant::now();
+
+        let results = run(&[hook("sleep 5", 1)], &theme);
+
+        assert_eq!(results, [Err("`sleep 5` timed out after 1s".to_string())]);
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn does_not_wait_for_background_processes() {
+        let theme = theme("background");
+        let started = Instant::now();
+
+        let results = run(&[hook("sleep 5 & echo started", 10)], &theme);
+
+        assert_eq!(
+            results,
+            [Ok("Ran `sleep 5 & echo started`\nstarted".to_string())]
+        );
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+}
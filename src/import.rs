@@ -0,0 +1,299 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::alacritty::AlacrittyTheme;
+use crate::config;
+use crate::theme::{Palette, Rgb};
+
+mod base16;
+mod foot;
+mod iterm;
+mod jsonc;
+mod kitty;
+mod vscode;
+pub mod windows_terminal;
+mod xresources;
+
+pub use base16::Mapping;
+
+/// A colour scheme read from the format of another terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedTheme {
+    pub name: String,
+    pub palette: Palette,
+    /// Keys of the scheme that have no place in an Alacritty theme
+    pub unmapped: Vec<String>,
+}
+
+impl ImportedTheme {
+    pub fn new(name: String, palette: Palette) -> Self {
+        Self {
+            name,
+            palette,
+            unmapped: Vec::new(),
+        }
+    }
+}
+
+/// The formats schemes are imported from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Iterm,
+    Json,
+    Base16,
+    Kitty,
+    Foot,
+    Xresources,
+}
+
+impl Format {
+    /// Guesses the format of a file from its extension, or its name for the
+    /// Xresources files that have none
+    fn of(path: &Path) -> Option<Self> {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "itermcolors" => Some(Format::Iterm),
+            "json" | "jsonc" => Some(Format::Json),
+            "yaml" | "yml" => Some(Format::Base16),
+            "conf" => Some(Format::Kitty),
+            "ini" => Some(Format::Foot),
+            "xresources" | "xdefaults" | "ad" => Some(Format::Xresources),
+            _ if matches!(
+                file_name.trim_start_matches('.'),
+                "xresources" | "xdefaults"
+            ) =>
+            {
+                Some(Format::Xresources)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The schemes read from a file or a directory
+#[derive(Debug, Default)]
+pub struct Imported {
+    pub themes: Vec<ImportedTheme>,
+    /// Files of the directory that could not be read
+    pub warnings: Vec<String>,
+}
+
+/// Reads the colour schemes of a file, or of every file of a directory in one
+/// of the known formats. The mapping applies to base16 schemes only.
+pub fn read(path: &Path, mapping: Option<Mapping>) -> Result<Imported, String> {
+    if !path.is_dir() {
+        return Ok(Imported {
+            themes: read_file(path, mapping)?,
+            warnings: Vec::new(),
+        });
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(path)
+        .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|file| Format::of(file).is_some())
+        .collect();
+    if files.is_empty() {
+        return Err(format!("{} has no colour scheme to import", path.display()));
+    }
+    files.sort();
+
+    let mut imported = Imported::default();
+    for file in files {
+        match read_file(&file, mapping) {
+            Ok(themes) => imported.themes.extend(themes),
+            Err(error) => imported.warnings.push(error),
+        }
+    }
+
+    Ok(imported)
+}
+
+/// Reads the colour schemes of a file, in a format guessed from its name and,
+/// for JSON, from its keys
+fn read_file(path: &Path, mapping: Option<Mapping>) -> Result<Vec<ImportedTheme>, String> {
+    let name = file_stem(path);
+    let Some(format) = Format::of(path) else {
+        return Err(format!(
+            "Unable to import {}: unknown format, expected an .itermcolors, .json, .yaml, \
+             kitty .conf, foot .ini or Xresources file",
+            path.display()
+        ));
+    };
+    let contents = || {
+        fs::read_to_string(path).map_err(|e| format!("Unable to read {}: {}", path.display(), e))
+    };
+    let themes = match format {
+        Format::Iterm => vec![ImportedTheme::new(name, iterm::read(path)?)],
+        Format::Json => read_json(path, &contents()?, name)?,
+        Format::Base16 => read_yaml(path, &contents()?, name, mapping)?,
+        Format::Kitty => vec![kitty::read(&contents()?, name)],
+        Format::Foot => foot::read(&contents()?, &name),
+        Format::Xresources => vec![xresources::read(&contents()?, name)],
+    };
+    if themes
+        .iter()
+        .all(|theme| theme.palette.entries().is_empty())
+    {
+        return Err(format!("{} has no colour scheme to import", path.display()));
+    }
+
+    Ok(themes)
+}
+
+/// Reads the schemes of a Windows Terminal `settings.json` or the terminal
+/// colours of a VS Code theme
+fn read_json(path: &Path, contents: &str, name: String) -> Result<Vec<ImportedTheme>, String> {
+    let json =
+        jsonc::parse(contents).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    let themes = match json {
+        _ if windows_terminal::detect(&json) => windows_terminal::read(&json),
+        _ if vscode::detect(&json) => vscode::read(&json, name).into_iter().collect(),
+        _ => {
+            return Err(format!(
+                "{} is neither a Windows Terminal nor a VS Code colour theme",
+                path.display()
+            ))
+        }
+    };
+
+    Ok(themes)
+}
+
+/// Reads a base16 or base24 scheme
+fn read_yaml(
+    path: &Path,
+    contents: &str,
+    name: String,
+    mapping: Option<Mapping>,
+) -> Result<Vec<ImportedTheme>, String> {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(contents)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    if !base16::detect(&yaml) {
+        return Err(format!(
+            "{} is not a base16 or base24 scheme",
+            path.display()
+        ));
+    }
+
+    base16::read(&yaml, name, mapping)
+        .map(|theme| vec![theme])
+        .map_err(|e| format!("Unable to import {}: {}", path.display(), e))
+}
+
+/// Parses a colour written as `#rrggbb`, `#rgb`, `#rrggbbaa` or the X11
+/// `rgb:rr/gg/bb`. Transparency has no equivalent in Alacritty themes and is
+/// dropped.
+fn parse_color(value: &str) -> Option<Rgb> {
+    let value = value.trim();
+    if let Some(channels) = value.strip_prefix("rgb:") {
+        let channels: Vec<&str> = channels.split('/').collect();
+        let [r, g, b] = channels.as_slice() else {
+            return None;
+        };
+        return Rgb::parse(&format!("#{}{}{}", r.get(..2)?, g.get(..2)?, b.get(..2)?));
+    }
+
+    match value.len() {
+        9 => Rgb::parse(value.get(..7)?),
+        _ => Rgb::parse(value),
+    }
+}
+
+/// Builds a theme from the `(key, value)` pairs of a key-value format.
+/// `slot` names the palette colour a key sets, as `colorN` for the ANSI
+/// colours or the name of the `Palette` field. Keys without one, or whose
+/// value is not a colour, are kept as unmapped.
+fn from_pairs(
+    name: String,
+    pairs: Vec<(String, String)>,
+    slot: impl Fn(&str) -> Option<String>,
+) -> ImportedTheme {
+    let mut theme = ImportedTheme::new(name, Palette::default());
+    for (key, value) in pairs {
+        let color = parse_color(&value).or_else(|| parse_color(&format!("#{}", value)));
+        let field = slot(&key).and_then(|slot| palette_field(&mut theme.palette, &slot));
+        match (field, color) {
+            (Some(field), Some(color)) => *field = Some(color),
+            _ if !theme.unmapped.contains(&key) => theme.unmapped.push(key),
+            _ => {}
+        }
+    }
+
+    theme
+}
+
+/// The colour of a palette named as in [`from_pairs`]
+fn palette_field<'a>(palette: &'a mut Palette, slot: &str) -> Option<&'a mut Option<Rgb>> {
+    if let Some(index) = slot.strip_prefix("color") {
+        return match index.parse::<usize>().ok()? {
+            index @ 0..=7 => Some(&mut palette.normal[index]),
+            index @ 8..=15 => Some(&mut palette.bright[index - 8]),
+            _ => None,
+        };
+    }
+
+    match slot {
+        "background" => Some(&mut palette.background),
+        "foreground" => Some(&mut palette.foreground),
+        "cursor" => Some(&mut palette.cursor),
+        "cursor_text" => Some(&mut palette.cursor_text),
+        "selection_background" => Some(&mut palette.selection_background),
+        "selection_text" => Some(&mut palette.selection_text),
+        _ => None,
+    }
+}
+
+/// Writes an imported theme into the user themes directory, refusing to
+/// replace a theme of the same name
+pub fn save(theme: &ImportedTheme) -> Result<AlacrittyTheme, String> {
+    let dir = config::themes_dir()
+        .ok_or_else(|| "Could not find the alacritty-skins themes directory".to_string())?;
+    let path = dir.join(format!("{}.toml", file_name(&theme.name)?));
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| format!("Unable to create {}: {}", dir.display(), e))?;
+    fs::write(&path, theme.palette.to_toml())
+        .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+
+    Ok(AlacrittyTheme::new(path))
+}
+
+/// Name of the scheme a file holds when the file does not name it
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().trim_start_matches('.').to_string())
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or_else(|| "Imported".to_string())
+}
+
+/// Turns a scheme name into a file name, without the separators that would
+/// place the theme elsewhere
+fn file_name(name: &str) -> Result<String, String> {
+    let file_name: String = name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' => '-',
+            c => c,
+        })
+        .collect();
+    if file_name.is_empty() || file_name.starts_with('.') {
+        return Err(format!("`{}` is not a valid theme name", name));
+    }
+
+    Ok(file_name)
+}
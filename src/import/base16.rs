@@ -0,0 +1,202 @@
+use clap::ValueEnum;
+use serde_yaml::Value;
+
+use super::{parse_color, ImportedTheme};
+use crate::theme::{Palette, Rgb};
+
+/// How the base16 colours are laid out on the terminal palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Mapping {
+    /// base16-shell: the bright colours repeat the normal ones, except for
+    /// black (base03) and white (base07)
+    Shell,
+    /// The classic base16 terminal templates: the bright colours hold the
+    /// shades and accents left over (base09, base01, base02, base04, base06,
+    /// base0F)
+    Classic,
+    /// base24: the bright colours come from base12 to base17
+    Base24,
+}
+
+/// Whether the YAML is a base16 or base24 scheme, in the flat layout or the
+/// tinted-theming one with a `palette` table
+pub fn detect(yaml: &Value) -> bool {
+    colors(yaml).get("base00").is_some()
+}
+
+/// The table holding the `baseXX` keys
+fn colors(yaml: &Value) -> &Value {
+    match yaml.get("palette") {
+        Some(palette) if palette.is_mapping() => palette,
+        _ => yaml,
+    }
+}
+
+/// Reads a base16 or base24 scheme. Without a mapping, base24 schemes use
+/// their bright colours and base16 ones the base16-shell layout.
+pub fn read(
+    yaml: &Value,
+    file_stem: String,
+    mapping: Option<Mapping>,
+) -> Result<ImportedTheme, String> {
+    let colors = colors(yaml);
+    let base = |index: usize| -> Result<Rgb, String> {
+        let key = format!("base{:02X}", index);
+        let value = colors
+            .get(&key)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("the scheme has no {}", key))?;
+        parse_color(value)
+            .or_else(|| parse_color(&format!("#{}", value)))
+            .ok_or_else(|| format!("{} is not a colour: {}", key, value))
+    };
+
+    let is_base24 = colors.get("base17").is_some();
+    let mapping = mapping.unwrap_or(match is_base24 {
+        true => Mapping::Base24,
+        false => Mapping::Shell,
+    });
+    if mapping == Mapping::Base24 && !is_base24 {
+        return Err("the base24 mapping needs a base24 scheme, this one is base16".to_string());
+    }
+
+    // black, red, green, yellow, blue, magenta, cyan, white
+    let normal = [0x00, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x05];
+    let bright = match mapping {
+        Mapping::Shell => [0x03, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x07],
+        Mapping::Classic => [0x03, 0x09, 0x01, 0x02, 0x04, 0x06, 0x0F, 0x07],
+        Mapping::Base24 => [0x03, 0x12, 0x14, 0x13, 0x16, 0x17, 0x15, 0x07],
+    };
+
+    let mut palette = Palette {
+        background: Some(base(0x00)?),
+        foreground: Some(base(0x05)?),
+        cursor: Some(base(0x05)?),
+        cursor_text: Some(base(0x00)?),
+        selection_background: Some(base(0x02)?),
+        selection_text: Some(base(0x05)?),
+        ..Palette::default()
+    };
+    for (color, index) in palette.normal.iter_mut().zip(normal) {
+        *color = Some(base(index)?);
+    }
+    for (color, index) in palette.bright.iter_mut().zip(bright) {
+        *color = Some(base(index)?);
+    }
+
+    let name = ["name", "scheme"]
+        .iter()
+        .find_map(|key| yaml.get(key).and_then(Value::as_str))
+        .map(String::from)
+        .unwrap_or(file_stem);
+    Ok(ImportedTheme::new(name, palette))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A scheme with `count` colours, where `baseXX` is `#0000XX` so every
+    /// slot tells which base colour it got
+    fn scheme(count: usize) -> String {
+        let mut yaml = "scheme: \"Test\"\n".to_string();
+        for index in 0..count {
+            yaml.push_str(&format!("base{:02X}: \"0000{:02x}\"\n", index, index));
+        }
+        yaml
+    }
+
+    fn read_scheme(yaml: &str, mapping: Option<Mapping>) -> Result<ImportedTheme, String> {
+        read(
+            &serde_yaml::from_str(yaml).unwrap(),
+            "file".to_string(),
+            mapping,
+        )
+    }
+
+    /// The base colour each slot was taken from
+    fn bases(colors: &[Option<Rgb>; 8]) -> [u8; 8] {
+        colors.map(|color| color.unwrap().b)
+    }
+
+    #[test]
+    fn shell_repeats_the_normal_colours() {
+        let theme = read_scheme(&scheme(16), None).unwrap();
+
+        assert_eq!(theme.name, "Test");
+        assert_eq!(
+            bases(&theme.palette.normal),
+            [0x00, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x05]
+        );
+        assert_eq!(
+            bases(&theme.palette.bright),
+            [0x03, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x07]
+        );
+        assert_eq!(theme.palette.background, Some(Rgb::new(0, 0, 0x00)));
+        assert_eq!(theme.palette.foreground, Some(Rgb::new(0, 0, 0x05)));
+        assert_eq!(
+            theme.palette.selection_background,
+            Some(Rgb::new(0, 0, 0x02))
+        );
+    }
+
+    #[test]
+    fn classic_uses_the_leftover_colours() {
+        let theme = read_scheme(&scheme(16), Some(Mapping::Classic)).unwrap();
+
+        assert_eq!(
+            bases(&theme.palette.bright),
+            [0x03, 0x09, 0x01, 0x02, 0x04, 0x06, 0x0F, 0x07]
+        );
+    }
+
+    #[test]
+    fn base24_schemes_use_their_bright_colours() {
+        let theme = read_scheme(&scheme(24), None).unwrap();
+
+        assert_eq!(
+            bases(&theme.palette.bright),
+            [0x03, 0x12, 0x14, 0x13, 0x16, 0x17, 0x15, 0x07]
+        );
+        // They can still be mapped like base16 ones
+        let theme = read_scheme(&scheme(24), Some(Mapping::Shell)).unwrap();
+        assert_eq!(bases(&theme.palette.bright)[1], 0x08);
+    }
+
+    #[test]
+    fn base24_mapping_needs_a_base24_scheme() {
+        assert_eq!(
+            read_scheme(&scheme(16), Some(Mapping::Base24)).unwrap_err(),
+            "the base24 mapping needs a base24 scheme, this one is base16"
+        );
+    }
+
+    #[test]
+    fn reads_the_palette_layout() {
+        let yaml = "name: \"Nested\"\npalette:\n  base00: \"#000000\"\n".to_string()
+            + &scheme(16)
+                .lines()
+                .skip(2)
+                .map(|line| format!("  {}\n", line))
+                .collect::<String>();
+        let yaml: Value = serde_yaml::from_str(&yaml).unwrap();
+
+        assert!(detect(&yaml));
+        let theme = read(&yaml, "file".to_string(), None).unwrap();
+        assert_eq!(theme.name, "Nested");
+        assert_eq!(theme.palette.normal[1], Some(Rgb::new(0, 0, 0x08)));
+    }
+
+    #[test]
+    fn reports_missing_and_invalid_colours() {
+        assert_eq!(
+            read_scheme(&scheme(15), Some(Mapping::Classic)).unwrap_err(),
+            "the scheme has no base0F"
+        );
+        let invalid = scheme(16).replace("00000a", "nope");
+        assert_eq!(
+            read_scheme(&invalid, None).unwrap_err(),
+            "base0A is not a colour: nope"
+        );
+    }
+}
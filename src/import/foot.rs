@@ -0,0 +1,134 @@
+use super::{from_pairs, ImportedTheme};
+
+/// Sections of a foot configuration holding colours. foot 1.24 splits
+/// `[colors]` into a dark and a light variant.
+const SECTIONS: [(&str, &str); 3] = [
+    ("colors", ""),
+    ("colors-dark", " (dark)"),
+    ("colors-light", " (light)"),
+];
+
+/// Reads the colour sections of a foot `foot.ini`, one theme per section
+pub fn read(contents: &str, name: &str) -> Vec<ImportedTheme> {
+    // The name suffix and pairs of each colour section
+    let mut sections: Vec<(&str, Vec<(String, String)>)> = Vec::new();
+    let mut in_colors = false;
+
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(section) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            let colors = SECTIONS.iter().find(|(name, _)| *name == section.trim());
+            if let Some((_, suffix)) = colors {
+                sections.push((suffix, Vec::new()));
+            }
+            in_colors = colors.is_some();
+            continue;
+        }
+        let (true, Some((key, value))) = (in_colors, line.split_once('=')) else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        let pairs = &mut sections.last_mut().expect("a section was pushed").1;
+
+        // `cursor` holds the text colour, then the cursor colour
+        match (key, value.split_once(char::is_whitespace)) {
+            ("cursor", Some((text, cursor))) => {
+                pairs.push(("cursor".to_string(), cursor.trim().to_string()));
+                pairs.push(("cursor-text".to_string(), text.to_string()));
+            }
+            _ => pairs.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(suffix, pairs)| from_pairs(format!("{}{}", name, suffix), pairs, slot))
+        .collect()
+}
+
+fn slot(key: &str) -> Option<String> {
+    let slot = match key {
+        "background" | "foreground" | "cursor" => key.to_string(),
+        "cursor-text" => "cursor_text".to_string(),
+        "selection-background" => "selection_background".to_string(),
+        "selection-foreground" => "selection_text".to_string(),
+        _ => {
+            let (prefix, offset) = match key {
+                _ if key.starts_with("regular") => ("regular", 0),
+                _ if key.starts_with("bright") => ("bright", 8),
+                _ => return None,
+            };
+            let index = key[prefix.len()..]
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < 8)?;
+            format!("color{}", index + offset)
+        }
+    };
+
+    Some(slot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Rgb;
+
+    #[test]
+    fn reads_each_colour_section() {
+        let themes = read(
+            "[main]\nfont=monospace:size=10\nbackground=ffffff\n\n\
+             [colors-dark]\nbackground=1d2021\ncursor=000000 ebdbb2\nregular1=cc241d\n\
+             bright1=fb4934\n\n[colors-light]\n# light\nbackground=fbf1c7\n",
+            "test",
+        );
+
+        assert_eq!(themes.len(), 2);
+        assert_eq!(themes[0].name, "test (dark)");
+        assert_eq!(
+            themes[0].palette.background,
+            Some(Rgb::new(0x1d, 0x20, 0x21))
+        );
+        assert_eq!(themes[0].palette.cursor, Some(Rgb::new(0xeb, 0xdb, 0xb2)));
+        assert_eq!(themes[0].palette.cursor_text, Some(Rgb::new(0, 0, 0)));
+        assert_eq!(
+            themes[0].palette.normal[1],
+            Some(Rgb::new(0xcc, 0x24, 0x1d))
+        );
+        assert_eq!(
+            themes[0].palette.bright[1],
+            Some(Rgb::new(0xfb, 0x49, 0x34))
+        );
+        assert_eq!(themes[1].name, "test (light)");
+        assert_eq!(
+            themes[1].palette.background,
+            Some(Rgb::new(0xfb, 0xf1, 0xc7))
+        );
+    }
+
+    #[test]
+    fn keeps_malformed_lines_as_unmapped() {
+        let themes = read(
+            "[colors]\nforeground\nregular8=ffffff\nbright1=nope\nalpha=0.9\nregular2 = 98971a\n",
+            "test",
+        );
+
+        // A line without `=` is skipped, a bad key or value is kept
+        assert_eq!(themes[0].unmapped, ["regular8", "bright1", "alpha"]);
+        assert_eq!(themes[0].palette.foreground, None);
+        assert_eq!(
+            themes[0].palette.normal[2],
+            Some(Rgb::new(0x98, 0x97, 0x1a))
+        );
+    }
+
+    #[test]
+    fn ignores_keys_outside_colour_sections() {
+        assert!(read("background=000000\n[main]\nforeground=ffffff\n", "test").is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use plist::{Dictionary, Value};
+
+use crate::theme::{Palette, Rgb};
+
+/// Reads an iTerm2 `.itermcolors` property list
+pub fn read(path: &Path) -> Result<Palette, String> {
+    let plist =
+        Value::from_file(path).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    let colors = plist
+        .as_dictionary()
+        .ok_or_else(|| format!("{} is not an iTerm2 colour scheme", path.display()))?;
+    let color = |key: &str| {
+        colors
+            .get(&format!("{} Color", key))
+            .and_then(Value::as_dictionary)
+            .and_then(to_rgb)
+    };
+
+    let palette = Palette {
+        background: color("Background"),
+        foreground: color("Foreground"),
+        normal: std::array::from_fn(|index| color(&format!("Ansi {}", index))),
+        bright: std::array::from_fn(|index| color(&format!("Ansi {}", index + 8))),
+        cursor: color("Cursor"),
+        cursor_text: color("Cursor Text"),
+        selection_background: color("Selection"),
+        selection_text: color("Selected Text"),
+    };
+    if palette.entries().is_empty() {
+        return Err(format!("{} has no colours to import", path.display()));
+    }
+
+    Ok(palette)
+}
+
+/// Converts a colour dictionary, whose components are floats between 0 and 1
+/// in the colour space it names. Display P3 colours are converted to sRGB;
+/// the other spaces iTerm2 writes are close enough to be taken as sRGB.
+fn to_rgb(color: &Dictionary) -> Option<Rgb> {
+    let component = |name: &str| {
+        let value = color.get(&format!("{} Component", name))?;
+        value
+            .as_real()
+            .or_else(|| value.as_signed_integer().map(|integer| integer as f64))
+    };
+    let mut rgb = [component("Red")?, component("Green")?, component("Blue")?];
+
+    if color.get("Color Space").and_then(Value::as_string) == Some("P3") {
+        rgb = p3_to_srgb(rgb);
+    }
+
+    let [r, g, b] = rgb.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+    Some(Rgb::new(r, g, b))
+}
+
+/// Converts a Display P3 colour to sRGB. Both use the same transfer curve and
+/// white point, only the primaries differ.
+fn p3_to_srgb(rgb: [f64; 3]) -> [f64; 3] {
+    const P3_TO_SRGB: [[f64; 3]; 3] = [
+        [1.2249, -0.2247, 0.0],
+        [-0.0420, 1.0419, 0.0],
+        [-0.0197, -0.0786, 1.0979],
+    ];
+
+    let linear = rgb.map(|channel| match channel <= 0.04045 {
+        true => channel / 12.92,
+        false => ((channel + 0.055) / 1.055).powf(2.4),
+    });
+    P3_TO_SRGB.map(|row| {
+        let channel: f64 = row.iter().zip(linear).map(|(a, b)| a * b).sum();
+        match channel <= 0.0031308 {
+            true => channel * 12.92,
+            false => 1.055 * channel.max(0.0).powf(1.0 / 2.4) - 0.055,
+        }
+    })
+}
@@ -0,0 +1,107 @@
+use serde_json::Value;
+
+/// Parses JSON with comments and trailing commas, as found in the settings of
+/// Windows Terminal and VS Code
+pub fn parse(contents: &str) -> Result<Value, String> {
+    serde_json::from_str(&strip(contents)).map_err(|e| e.to_string())
+}
+
+/// Removes the `//` and `/* */` comments, and the commas right before a
+/// closing bracket, leaving the contents of strings alone
+fn strip(contents: &str) -> String {
+    let mut stripped = String::with_capacity(contents.len());
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                stripped.push(c);
+                while let Some(c) = chars.next() {
+                    stripped.push(c);
+                    match c {
+                        '\\' => stripped.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                stripped.push(' ');
+            }
+            '}' | ']' => {
+                let trimmed = stripped.trim_end().len();
+                if stripped[..trimmed].ends_with(',') {
+                    stripped.remove(trimmed - 1);
+                }
+                stripped.push(c);
+            }
+            _ => stripped.push(c),
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn strips_comments() {
+        let contents = "{\n  // line comment\n  \"a\": 1, /* block\n comment */ \"b\": 2\n}";
+
+        assert_eq!(parse(contents).unwrap(), json!({"a": 1, "b": 2}));
+    }
+
+    #[test]
+    fn leaves_strings_alone() {
+        let contents = r#"{
+            "url": "https://example.com/*not a comment*/",
+            "path": "C:\\dir\\",
+            "quote": "say \"// hi\"",
+            "comma": "a,]"
+        }"#;
+
+        assert_eq!(
+            parse(contents).unwrap(),
+            json!({
+                "url": "https://example.com/*not a comment*/",
+                "path": "C:\\dir\\",
+                "quote": "say \"// hi\"",
+                "comma": "a,]",
+            })
+        );
+    }
+
+    #[test]
+    fn drops_trailing_commas() {
+        let contents = "{\"list\": [1, 2, // two\n], \"object\": {\"a\": 1,},}";
+
+        assert_eq!(
+            parse(contents).unwrap(),
+            json!({"list": [1, 2], "object": {"a": 1}})
+        );
+    }
+
+    #[test]
+    fn comment_at_the_end_of_the_file() {
+        assert_eq!(parse("[1] // done").unwrap(), json!([1]));
+        assert_eq!(parse("[1] /* unterminated").unwrap(), json!([1]));
+    }
+
+    #[test]
+    fn reports_invalid_json() {
+        assert!(parse("{\"a\": }").is_err());
+    }
+}
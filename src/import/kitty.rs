@@ -0,0 +1,60 @@
+use super::{from_pairs, ImportedTheme};
+
+/// Reads the colours of a kitty configuration or theme, lines of
+/// `key value` with `#` comments
+pub fn read(contents: &str, name: String) -> ImportedTheme {
+    let pairs = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once(char::is_whitespace)?;
+            Some((key.to_string(), value.trim().to_string()))
+        })
+        .collect();
+
+    from_pairs(name, pairs, |key| {
+        let slot = match key {
+            "background" | "foreground" | "cursor" => key,
+            "cursor_text_color" => "cursor_text",
+            "selection_background" => "selection_background",
+            "selection_foreground" => "selection_text",
+            _ if key.starts_with("color") => key,
+            _ => return None,
+        };
+        Some(slot.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Rgb;
+
+    #[test]
+    fn reads_colours_and_skips_comments() {
+        let theme = read(
+            "# Test theme\n\n  background   #1d2021\ncolor1 #cc241d\ncursor_text_color #000\n",
+            "test".to_string(),
+        );
+
+        assert_eq!(theme.palette.background, Some(Rgb::new(0x1d, 0x20, 0x21)));
+        assert_eq!(theme.palette.normal[1], Some(Rgb::new(0xcc, 0x24, 0x1d)));
+        assert_eq!(theme.palette.cursor_text, Some(Rgb::new(0, 0, 0)));
+        assert!(theme.unmapped.is_empty());
+    }
+
+    #[test]
+    fn keeps_malformed_lines_as_unmapped() {
+        let theme = read(
+            "foreground\ncolor2 notacolour\ncolor16 #ffffff\nfont_size 12\nfont_size 13\n\
+             color3 #d79921\n",
+            "test".to_string(),
+        );
+
+        // A key without value is not a pair at all
+        assert_eq!(theme.unmapped, ["color2", "color16", "font_size"]);
+        assert_eq!(theme.palette.foreground, None);
+        assert_eq!(theme.palette.normal[3], Some(Rgb::new(0xd7, 0x99, 0x21)));
+    }
+}
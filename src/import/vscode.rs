@@ -0,0 +1,52 @@
+use serde_json::Value;
+
+use super::{parse_color, ImportedTheme};
+use crate::theme::Palette;
+
+/// Suffixes of the `terminal.ansi*` colours of VS Code, in palette order
+const ANSI_KEYS: [&str; 8] = [
+    "Black", "Red", "Green", "Yellow", "Blue", "Magenta", "Cyan", "White",
+];
+
+/// Whether the JSON is a VS Code colour theme
+pub fn detect(json: &Value) -> bool {
+    json.get("colors").is_some_and(Value::is_object)
+}
+
+/// Reads the integrated terminal colours of a VS Code colour theme. Like VS
+/// Code, the terminal falls back to the editor background and foreground.
+pub fn read(json: &Value, file_stem: String) -> Option<ImportedTheme> {
+    let colors = json.get("colors")?;
+    let color = |key: &str| {
+        colors
+            .get(key)
+            .and_then(Value::as_str)
+            .and_then(parse_color)
+    };
+
+    let palette = Palette {
+        background: color("terminal.background").or_else(|| color("editor.background")),
+        foreground: color("terminal.foreground").or_else(|| color("editor.foreground")),
+        normal: ANSI_KEYS.map(|key| color(&format!("terminal.ansi{}", key))),
+        bright: ANSI_KEYS.map(|key| color(&format!("terminal.ansiBright{}", key))),
+        cursor: color("terminalCursor.foreground"),
+        cursor_text: color("terminalCursor.background"),
+        selection_background: color("terminal.selectionBackground"),
+        selection_text: color("terminal.selectionForeground"),
+    };
+    if palette
+        .normal
+        .iter()
+        .chain(&palette.bright)
+        .all(Option::is_none)
+    {
+        return None;
+    }
+
+    let name = json
+        .get("name")
+        .and_then(Value::as_str)
+        .map(String::from)
+        .unwrap_or(file_stem);
+    Some(ImportedTheme::new(name, palette))
+}
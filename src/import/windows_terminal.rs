@@ -0,0 +1,56 @@
+use serde_json::Value;
+
+use super::{parse_color, ImportedTheme};
+use crate::theme::Palette;
+
+/// Names Windows Terminal gives the ANSI colours, in palette order
+pub const ANSI_KEYS: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "purple", "cyan", "white",
+];
+
+/// Names of the bright ANSI colours, as `brightBlack`, in palette order
+pub fn bright_keys() -> [String; 8] {
+    ANSI_KEYS.map(|key| {
+        let mut chars = key.chars();
+        let first = chars.next().map(|c| c.to_ascii_uppercase());
+        format!("bright{}{}", first.unwrap_or_default(), chars.as_str())
+    })
+}
+
+/// Whether the JSON is a Windows Terminal `settings.json` or a lone scheme of
+/// one
+pub fn detect(json: &Value) -> bool {
+    json.get("schemes").is_some() || (json.get("name").is_some() && json.get("black").is_some())
+}
+
+/// Reads the `schemes` of a `settings.json`, or a lone scheme
+pub fn read(json: &Value) -> Vec<ImportedTheme> {
+    match json.get("schemes").and_then(Value::as_array) {
+        Some(schemes) => schemes.iter().filter_map(scheme).collect(),
+        None => scheme(json).into_iter().collect(),
+    }
+}
+
+fn scheme(scheme: &Value) -> Option<ImportedTheme> {
+    let name = scheme.get("name")?.as_str()?.to_string();
+    let color = |key: &str| {
+        scheme
+            .get(key)
+            .and_then(Value::as_str)
+            .and_then(parse_color)
+    };
+
+    Some(ImportedTheme::new(
+        name,
+        Palette {
+            background: color("background"),
+            foreground: color("foreground"),
+            normal: ANSI_KEYS.map(color),
+            bright: bright_keys().map(|key| color(&key)),
+            cursor: color("cursorColor"),
+            cursor_text: None,
+            selection_background: color("selectionBackground"),
+            selection_text: None,
+        },
+    ))
+}
@@ -0,0 +1,88 @@
+use super::{from_pairs, ImportedTheme};
+
+/// Reads the colours of an Xresources file, lines of `*.color0: #282828`
+/// whose values may be `#define` macros. `!` starts a comment.
+pub fn read(contents: &str, name: String) -> ImportedTheme {
+    let mut defines: Vec<(&str, &str)> = Vec::new();
+    let mut pairs = Vec::new();
+
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('!') {
+            continue;
+        }
+        if let Some(define) = line.strip_prefix("#define") {
+            let mut parts = define.split_whitespace();
+            if let (Some(macro_name), Some(value)) = (parts.next(), parts.next()) {
+                defines.push((macro_name, value));
+            }
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((resource, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        // Later definitions of a macro win, as with the C preprocessor
+        let value = value.trim();
+        let value = defines
+            .iter()
+            .rev()
+            .find(|(macro_name, _)| *macro_name == value)
+            .map_or(value, |(_, expansion)| *expansion);
+        pairs.push((resource.trim().to_string(), value.to_string()));
+    }
+
+    from_pairs(name, pairs, slot)
+}
+
+/// Maps a resource such as `*.color0`, `URxvt*background` or
+/// `XTerm.vt100.cursorColor` by its last component
+fn slot(resource: &str) -> Option<String> {
+    let name = resource.rsplit(['.', '*']).next().unwrap_or(resource);
+    let slot = match name {
+        "background" | "foreground" => name,
+        "cursorColor" => "cursor",
+        "highlightColor" => "selection_background",
+        "highlightTextColor" => "selection_text",
+        _ if name.starts_with("color") => name,
+        _ => return None,
+    };
+
+    Some(slot.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Rgb;
+
+    #[test]
+    fn expands_defines_and_maps_resources() {
+        let theme = read(
+            "! Test theme\n#define red #cc241d\n#define red #fb4934\n\
+             *.color1: red\nURxvt*background: #1d2021\nXTerm.vt100.cursorColor: #ebdbb2\n",
+            "test".to_string(),
+        );
+
+        assert_eq!(theme.palette.normal[1], Some(Rgb::new(0xfb, 0x49, 0x34)));
+        assert_eq!(theme.palette.background, Some(Rgb::new(0x1d, 0x20, 0x21)));
+        assert_eq!(theme.palette.cursor, Some(Rgb::new(0xeb, 0xdb, 0xb2)));
+        assert!(theme.unmapped.is_empty());
+    }
+
+    #[test]
+    fn keeps_malformed_lines_as_unmapped() {
+        let theme = read(
+            "*.foreground #ebdbb2\n#define\n#include \"other\"\n*.color2: green\n\
+             *.color16: #ffffff\nURxvt.font: xft:monospace\n*.color3: #d79921\n",
+            "test".to_string(),
+        );
+
+        // Lines without `:` and incomplete macros are skipped
+        assert_eq!(theme.unmapped, ["*.color2", "*.color16", "URxvt.font"]);
+        assert_eq!(theme.palette.foreground, None);
+        assert_eq!(theme.palette.normal[3], Some(Rgb::new(0xd7, 0x99, 0x21)));
+    }
+}
@@ -0,0 +1,592 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::action::Action;
+
+/// Part of the interface in which a key binding is active
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Global,
+    Themes,
+}
+
+impl Scope {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Scope::Global => "Global",
+            Scope::Themes => "Themes",
+        }
+    }
+
+    /// Name of the scope's table in the `[keymap]` configuration section
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scope::Global => "global",
+            Scope::Themes => "themes",
+        }
+    }
+}
+
+/// A single key press, including its modifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn from_event(event: &KeyEvent) -> Self {
+        // Shift is already reflected in the character itself ('G', '?'), and
+        // terminals disagree on whether they report it, so ignore it there
+        let modifiers = match event.code {
+            KeyCode::Char(_) | KeyCode::BackTab => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+
+    /// Formats the key the way it is shown to the user
+    pub fn label(&self) -> String {
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl-");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt-");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push_str("Shift-");
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => label.push_str("Space"),
+            KeyCode::Char(c) => label.push(c),
+            KeyCode::Up => label.push('↑'),
+            KeyCode::Down => label.push('↓'),
+            KeyCode::Left => label.push('←'),
+            KeyCode::Right => label.push('→'),
+            KeyCode::F(n) => label.push_str(&format!("F{}", n)),
+            other => label.push_str(&format!("{:?}", other)),
+        }
+
+        label
+    }
+}
+
+/// Parses a key chord such as `ctrl-d`, `gg` or `ctrl-x ctrl-c` into the
+/// sequence of keys that must be pressed.
+///
+/// Keys are separated by whitespace. A token made of plain characters that is
+/// not the name of a key is read as one key per character, so `gg` is the same
+/// chord as `g g`.
+pub fn parse_chord(chord: &str) -> Result<Vec<Key>, String> {
+    let mut keys = Vec::new();
+
+    for token in chord.split_whitespace() {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = token;
+        while let Some((prefix, key)) = rest.split_once('-') {
+            let modifier = match prefix.to_lowercase().as_str() {
+                "ctrl" | "c" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "m" | "a" => KeyModifiers::ALT,
+                "shift" | "s" => KeyModifiers::SHIFT,
+                _ => break,
+            };
+            if key.is_empty() {
+                break;
+            }
+            modifiers |= modifier;
+            rest = key;
+        }
+
+        if let Some(code) = parse_key_name(rest) {
+            let key = match code {
+                KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => Key {
+                    code: KeyCode::BackTab,
+                    modifiers: modifiers - KeyModifiers::SHIFT,
+                },
+                KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => Key {
+                    code: KeyCode::Char(c.to_ascii_uppercase()),
+                    modifiers: modifiers - KeyModifiers::SHIFT,
+                },
+                code => Key { code, modifiers },
+            };
+            keys.push(key);
+        } else if modifiers == KeyModifiers::NONE && rest.chars().count() > 1 {
+            keys.extend(rest.chars().map(|c| Key {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::NONE,
+            }));
+        } else {
+            return Err(format!("invalid key `{}` in chord `{}`", rest, chord));
+        }
+    }
+
+    if keys.is_empty() {
+        return Err("empty key chord".to_string());
+    }
+
+    Ok(keys)
+}
+
+fn parse_key_name(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let code = match name.to_lowercase().as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "space" => KeyCode::Char(' '),
+        "minus" => KeyCode::Char('-'),
+        other => {
+            let n = other.strip_prefix('f')?.parse::<u8>().ok()?;
+            KeyCode::F(n)
+        }
+    };
+
+    Some(code)
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub keys: Vec<Key>,
+    pub action: Action,
+    pub scope: Scope,
+}
+
+impl Binding {
+    /// Formats the chord of the binding the way it is shown to the user
+    pub fn key_label(&self) -> String {
+        self.keys
+            .iter()
+            .map(Key::label)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Outcome of looking up a sequence of pressed keys in the keymap
+pub enum Resolution {
+    /// The keys form a complete chord bound to this action
+    Action(Action),
+    /// The keys are the beginning of at least one longer chord
+    Pending,
+    Unbound,
+}
+
+/// A `(scope, chord, command)` entry of a preset
+type PresetBinding = (Scope, &'static str, &'static str);
+
+/// Bindings shipped with alacritty-skins, selected with `keymap.preset`
+pub const PRESETS: &[(&str, &[PresetBinding])] = &[
+    (
+        "vim",
+        &[
+            (Scope::Global, "q", "quit"),
+            (Scope::Global, "?", "help"),
+            (Scope::Global, ":", "command-palette"),
+            (Scope::Global, "ctrl-p", "command-palette"),
+            (Scope::Global, "gt", "next-tab"),
+            (Scope::Global, "gT", "previous-tab"),
+            (Scope::Global, "R", "enable-live-reload"),
+            (Scope::Themes, "down", "select-next"),
+            (Scope::Themes, "up", "select-previous"),
+            (Scope::Themes, "j", "select-next"),
+            (Scope::Themes, "k", "select-previous"),
+            (Scope::Themes, "enter", "apply"),
+            (Scope::Themes, "space", "toggle-favorite"),
+            (Scope::Themes, "f", "filter"),
+            (Scope::Themes, "gg", "select-first"),
+            (Scope::Themes, "G", "select-last"),
+            (Scope::Themes, "home", "select-first"),
+            (Scope::Themes, "end", "select-last"),
+            (Scope::Themes, "ctrl-d", "half-page-down"),
+            (Scope::Themes, "ctrl-u", "half-page-up"),
+            (Scope::Themes, "pagedown", "page-down"),
+            (Scope::Themes, "pageup", "page-up"),
+            (Scope::Themes, "/", "search"),
+            (Scope::Themes, "n", "next-match"),
+            (Scope::Themes, "N", "previous-match"),
+            (Scope::Themes, "m", "set-mark"),
+            (Scope::Themes, "'", "jump-to-mark"),
+            (Scope::Themes, "`", "jump-to-mark"),
+            (Scope::Themes, "r", "random"),
+            (Scope::Themes, "t", "toggle-light-dark"),
+            (Scope::Themes, "u", "restore-backup"),
+            (Scope::Themes, "e", "open-in-editor"),
+            (Scope::Themes, "x", "export"),
+        ],
+    ),
+    (
+        "emacs",
+        &[
+            (Scope::Global, "ctrl-x ctrl-c", "quit"),
+            (Scope::Global, "f1", "help"),
+            (Scope::Global, "?", "help"),
+            (Scope::Global, "alt-x", "command-palette"),
+            (Scope::Global, ":", "command-palette"),
+            (Scope::Global, "ctrl-x o", "next-tab"),
+            (Scope::Global, "alt-r", "enable-live-reload"),
+            (Scope::Themes, "down", "select-next"),
+            (Scope::Themes, "up", "select-previous"),
+            (Scope::Themes, "ctrl-n", "select-next"),
+            (Scope::Themes, "ctrl-p", "select-previous"),
+            (Scope::Themes, "enter", "apply"),
+            (Scope::Themes, "ctrl-c f", "toggle-favorite"),
+            (Scope::Themes, "ctrl-s", "filter"),
+            (Scope::Themes, "alt-<", "select-first"),
+            (Scope::Themes, "alt->", "select-last"),
+            (Scope::Themes, "home", "select-first"),
+            (Scope::Themes, "end", "select-last"),
+            (Scope::Themes, "ctrl-v", "page-down"),
+            (Scope::Themes, "alt-v", "page-up"),
+            (Scope::Themes, "pagedown", "page-down"),
+            (Scope::Themes, "pageup", "page-up"),
+            (Scope::Themes, "alt-s", "search"),
+            (Scope::Themes, "alt-n", "next-match"),
+            (Scope::Themes, "alt-p", "previous-match"),
+            (Scope::Themes, "ctrl-space", "set-mark"),
+            (Scope::Themes, "ctrl-x j", "jump-to-mark"),
+        ],
+    ),
+];
+
+pub const DEFAULT_PRESET: &str = "vim";
+
+/// Table of every key binding of the application. Both key dispatch and the
+/// help overlay read from it, so they cannot disagree.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(DEFAULT_PRESET).expect("default preset exists")
+    }
+}
+
+impl Keymap {
+    /// Builds the keymap of one of the [`PRESETS`]
+    pub fn preset(name: &str) -> Option<Self> {
+        let (_, entries) = PRESETS.iter().find(|(preset, _)| *preset == name)?;
+        let bindings = entries
+            .iter()
+            .map(|(scope, chord, command)| Binding {
+                keys: parse_chord(chord).expect("preset chords are valid"),
+                action: Action::from_name(command).expect("preset commands exist"),
+                scope: *scope,
+            })
+            .collect();
+
+        Some(Self { bindings })
+    }
+
+    /// Builds a keymap from a preset and user supplied `(scope, chord, command)`
+    /// entries. User bindings replace the preset bindings they would clash
+    /// with, in their own scope and across the global one, whose chords are
+    /// active in every scope. Every problem found is reported at once.
+    pub fn with_overrides(
+        preset: &str,
+        overrides: &[(Scope, String, String)],
+    ) -> Result<Self, String> {
+        let mut keymap = Self::preset(preset).ok_or_else(|| {
+            let names: Vec<&str> = PRESETS.iter().map(|(name, _)| *name).collect();
+            format!(
+                "unknown keymap preset `{}` (available: {})",
+                preset,
+                names.join(", ")
+            )
+        })?;
+
+        let mut errors = Vec::new();
+        let mut user_bindings: Vec<(Binding, &str)> = Vec::new();
+
+        for (scope, chord, command) in overrides {
+            let keys = match parse_chord(chord) {
+                Ok(keys) => keys,
+                Err(error) => {
+                    errors.push(format!("[keymap.{}] {}", scope.name(), error));
+                    continue;
+                }
+            };
+            let action = match Action::from_name(command) {
+                Some(action) => action,
+                None => {
+                    errors.push(format!(
+                        "[keymap.{}] unknown command `{}` bound to `{}`",
+                        scope.name(),
+                        command,
+                        chord
+                    ));
+                    continue;
+                }
+            };
+
+            let binding = Binding {
+                keys,
+                action,
+                scope: *scope,
+            };
+            for (other, other_chord) in &user_bindings {
+                if !bindings_clash(other, &binding) {
+                    continue;
+                }
+                match other.scope == binding.scope {
+                    true => errors.push(format!(
+                        "[keymap.{}] `{}` conflicts with `{}`",
+                        scope.name(),
+                        chord,
+                        other_chord
+                    )),
+                    false => errors.push(format!(
+                        "[keymap.{}] `{}` conflicts with `{}` of [keymap.{}]",
+                        scope.name(),
+                        chord,
+                        other_chord,
+                        other.scope.name()
+                    )),
+                }
+            }
+            user_bindings.push((binding, chord));
+        }
+
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+
+        for (binding, _) in user_bindings {
+            keymap
+                .bindings
+                .retain(|preset| !bindings_clash(preset, &binding));
+            keymap.bindings.push(binding);
+        }
+
+        Ok(keymap)
+    }
+
+    /// Finds what the keys pressed so far resolve to, preferring the bindings
+    /// of the focused scope over the global ones
+    pub fn resolve(&self, scope: Scope, keys: &[Key]) -> Resolution {
+        let mut pending = false;
+
+        for scope in [scope, Scope::Global] {
+            for binding in self.bindings(scope) {
+                if binding.keys == keys {
+                    return Resolution::Action(binding.action.clone());
+                }
+                pending |= binding.keys.starts_with(keys);
+            }
+        }
+
+        match pending {
+            true => Resolution::Pending,
+            false => Resolution::Unbound,
+        }
+    }
+
+    /// All the bindings active in the given scope
+    pub fn bindings(&self, scope: Scope) -> impl Iterator<Item = &Binding> {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.scope == scope)
+    }
+
+    /// Label of the first chord bound to an action, if any
+    pub fn label_for(&self, scope: Scope, action: &Action) -> Option<String> {
+        self.bindings(scope)
+            .find(|binding| binding.action == *action)
+            .map(Binding::key_label)
+    }
+}
+
+/// Two chords clash when one of them can never be completed because the other
+/// one is triggered first
+fn chords_clash(a: &[Key], b: &[Key]) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
+/// Two bindings clash when their chords do and both are active in a scope:
+/// they share it, or one of them is global
+fn bindings_clash(a: &Binding, b: &Binding) -> bool {
+    let scopes_overlap = a.scope == b.scope || a.scope == Scope::Global || b.scope == Scope::Global;
+    scopes_overlap && chords_clash(&a.keys, &b.keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> Key {
+        Key {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn overrides(entries: &[(Scope, &str, &str)]) -> Vec<(Scope, String, String)> {
+        entries
+            .iter()
+            .map(|(scope, chord, command)| (*scope, chord.to_string(), command.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_modifiers_and_sequences() {
+        assert_eq!(parse_chord("gg").unwrap(), vec![key('g'), key('g')]);
+        assert_eq!(parse_chord("g g").unwrap(), vec![key('g'), key('g')]);
+        assert_eq!(
+            parse_chord("ctrl-x ctrl-c").unwrap(),
+            vec![
+                Key {
+                    code: KeyCode::Char('x'),
+                    modifiers: KeyModifiers::CONTROL,
+                },
+                Key {
+                    code: KeyCode::Char('c'),
+                    modifiers: KeyModifiers::CONTROL,
+                },
+            ]
+        );
+        assert_eq!(parse_chord("shift-g").unwrap(), vec![key('G')]);
+        assert_eq!(
+            parse_chord("shift-tab").unwrap(),
+            vec![Key {
+                code: KeyCode::BackTab,
+                modifiers: KeyModifiers::NONE,
+            }]
+        );
+        assert_eq!(parse_chord("-").unwrap(), vec![key('-')]);
+    }
+
+    #[test]
+    fn rejects_invalid_chords() {
+        assert_eq!(parse_chord("").unwrap_err(), "empty key chord");
+        assert_eq!(parse_chord("   ").unwrap_err(), "empty key chord");
+        assert_eq!(
+            parse_chord("ctrl-nope").unwrap_err(),
+            "invalid key `nope` in chord `ctrl-nope`"
+        );
+    }
+
+    #[test]
+    fn reports_every_override_problem() {
+        let error = Keymap::with_overrides(
+            "vim",
+            &overrides(&[
+                (Scope::Global, "ctrl-xy", "quit"),
+                (Scope::Themes, "x", "no-such-command"),
+            ]),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            "[keymap.global] invalid key `xy` in chord `ctrl-xy`\n\
+             [keymap.themes] unknown command `no-such-command` bound to `x`"
+        );
+    }
+
+    #[test]
+    fn reports_conflicting_overrides() {
+        let error = Keymap::with_overrides(
+            "vim",
+            &overrides(&[
+                (Scope::Themes, "g", "apply"),
+                (Scope::Themes, "gs", "random"),
+                (Scope::Global, "gx", "help"),
+            ]),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            "[keymap.themes] `gs` conflicts with `g`\n\
+             [keymap.global] `gx` conflicts with `g` of [keymap.themes]"
+        );
+    }
+
+    #[test]
+    fn overrides_replace_clashing_preset_bindings() {
+        let keymap =
+            Keymap::with_overrides("vim", &overrides(&[(Scope::Global, "g", "help")])).unwrap();
+
+        // The global `gt` and the themes `gg` started with `g` and are gone
+        for scope in [Scope::Global, Scope::Themes] {
+            assert!(matches!(
+                keymap.resolve(scope, &[key('g')]),
+                Resolution::Action(Action::Help)
+            ));
+            assert!(keymap
+                .bindings(scope)
+                .all(|binding| binding.keys.len() == 1 || binding.keys[0] != key('g')));
+        }
+    }
+
+    #[test]
+    fn scoped_overrides_replace_clashing_global_bindings() {
+        let ctrl_p = parse_chord("ctrl-p").unwrap();
+        let keymap = Keymap::with_overrides(
+            "vim",
+            &overrides(&[(Scope::Themes, "ctrl-p", "select-previous")]),
+        )
+        .unwrap();
+
+        assert!(keymap
+            .bindings(Scope::Global)
+            .all(|binding| binding.keys != ctrl_p));
+        assert!(matches!(
+            keymap.resolve(Scope::Themes, &ctrl_p),
+            Resolution::Action(Action::SelectPrevious)
+        ));
+        // The command palette keeps its other key
+        assert_eq!(
+            keymap.label_for(Scope::Global, &Action::CommandPalette),
+            Some(":".to_string())
+        );
+    }
+
+    #[test]
+    fn presets_have_no_clashing_bindings() {
+        for (name, _) in PRESETS {
+            let keymap = Keymap::preset(name).unwrap();
+            for (index, binding) in keymap.bindings.iter().enumerate() {
+                for other in &keymap.bindings[index + 1..] {
+                    assert!(
+                        !bindings_clash(binding, other),
+                        "{}: `{}` clashes with `{}`",
+                        name,
+                        binding.key_label(),
+                        other.key_label()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_presets() {
+        assert_eq!(
+            Keymap::with_overrides("nano", &[]).unwrap_err(),
+            "unknown keymap preset `nano` (available: vim, emacs)"
+        );
+    }
+}
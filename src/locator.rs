@@ -0,0 +1,286 @@
+use std::{collections::HashMap, env, path::PathBuf};
+
+/// Environment variables the locators read, injected so the search order of
+/// any platform can be computed anywhere
+pub type Environment = HashMap<String, String>;
+
+/// Names of the configuration file, in the order Alacritty prefers them: a
+/// TOML file anywhere wins over a legacy YAML one
+const FILE_NAMES: [&str; 3] = ["alacritty.toml", "alacritty.yml", "alacritty.yaml"];
+
+/// Directory of the system-wide configuration on the Unixes, searched last
+const SYSTEM_DIR: &str = "/etc/alacritty";
+
+/// Knows where Alacritty looks for its configuration on a platform
+pub trait ConfigLocator {
+    fn platform(&self) -> &'static str;
+
+    /// Locations searched for a configuration file of the given name, in order
+    fn locations(&self, env: &Environment, file_name: &str) -> Vec<PathBuf>;
+
+    /// Every candidate location, in the order Alacritty searches them
+    fn candidates(&self, env: &Environment) -> Vec<PathBuf> {
+        FILE_NAMES
+            .iter()
+            .flat_map(|file_name| self.locations(env, file_name))
+            .collect()
+    }
+
+    /// Where a new configuration file goes: the first location of the user's
+    /// own, never the system-wide one
+    fn preferred(&self, env: &Environment) -> Option<PathBuf> {
+        self.locations(env, FILE_NAMES[0])
+            .into_iter()
+            .find(|path| !path.starts_with(SYSTEM_DIR))
+    }
+}
+
+/// Linux and the BSDs, following the XDG base directories
+pub struct UnixLocator;
+
+/// macOS, where Alacritty follows the XDG layout like the other Unixes rather
+/// than `~/Library/Application Support`
+pub struct MacOsLocator;
+
+pub struct WindowsLocator;
+
+/// Non-empty value of an environment variable
+fn var<'a>(env: &'a Environment, name: &str) -> Option<&'a str> {
+    env.get(name)
+        .map(String::as_str)
+        .filter(|value| !value.is_empty())
+}
+
+/// The XDG search order shared by every Unix, ending with the system-wide
+/// configuration
+fn xdg_locations(env: &Environment, file_name: &str) -> Vec<PathBuf> {
+    let mut locations = Vec::new();
+
+    // $XDG_CONFIG_HOME/alacritty/alacritty.toml
+    // $XDG_CONFIG_HOME/alacritty.toml
+    if let Some(config_home) = var(env, "XDG_CONFIG_HOME") {
+        locations.push(format!("{}/alacritty/{}", config_home, file_name));
+        locations.push(format!("{}/{}", config_home, file_name));
+    }
+
+    // $HOME/.config/alacritty/alacritty.toml
+    // $HOME/.alacritty.toml
+    if let Some(home) = var(env, "HOME") {
+        locations.push(format!("{}/.config/alacritty/{}", home, file_name));
+        locations.push(format!("{}/.{}", home, file_name));
+    }
+
+    // /etc/alacritty/alacritty.toml
+    locations.push(format!("{}/{}", SYSTEM_DIR, file_name));
+
+    // XDG_CONFIG_HOME is often set to $HOME/.config, list each location once
+    let mut unique: Vec<PathBuf> = Vec::new();
+    for location in locations.into_iter().map(PathBuf::from) {
+        if !unique.contains(&location) {
+            unique.push(location);
+        }
+    }
+
+    unique
+}
+
+impl ConfigLocator for UnixLocator {
+    fn platform(&self) -> &'static str {
+        "unix"
+    }
+
+    fn locations(&self, env: &Environment, file_name: &str) -> Vec<PathBuf> {
+        xdg_locations(env, file_name)
+    }
+}
+
+impl ConfigLocator for MacOsLocator {
+    fn platform(&self) -> &'static str {
+        "macos"
+    }
+
+    fn locations(&self, env: &Environment, file_name: &str) -> Vec<PathBuf> {
+        xdg_locations(env, file_name)
+    }
+}
+
+impl ConfigLocator for WindowsLocator {
+    fn platform(&self) -> &'static str {
+        "windows"
+    }
+
+    fn locations(&self, env: &Environment, file_name: &str) -> Vec<PathBuf> {
+        // %APPDATA%\alacritty\alacritty.toml
+        var(env, "APPDATA")
+            .map(|appdata| PathBuf::from(format!("{}\\alacritty\\{}", appdata, file_name)))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// The locator for an operating system, as named by `std::env::consts::OS`
+pub fn for_os(os: &str) -> Result<Box<dyn ConfigLocator>, String> {
+    match os {
+        "linux" | "freebsd" | "openbsd" | "netbsd" | "dragonfly" => Ok(Box::new(UnixLocator)),
+        "macos" => Ok(Box::new(MacOsLocator)),
+        "windows" => Ok(Box::new(WindowsLocator)),
+        other => Err(format!("Unsupported platform: {}", other)),
+    }
+}
+
+/// The locator for the platform alacritty-skins runs on
+pub fn current() -> Result<Box<dyn ConfigLocator>, String> {
+    for_os(env::consts::OS)
+}
+
+/// The environment of the running process
+pub fn environment() -> Environment {
+    env::vars().collect()
+}
+
+/// Finds the configuration file Alacritty would load
+pub fn find_config(locator: &dyn ConfigLocator, env: &Environment) -> Result<PathBuf, String> {
+    locator
+        .candidates(env)
+        .into_iter()
+        .find(|path| path.exists())
+        .ok_or_else(|| String::from("Could not find configuration file"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> Environment {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn paths(paths: &[&str]) -> Vec<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn unix_searches_xdg_config_home_first() {
+        let env = env(&[("XDG_CONFIG_HOME", "/xdg"), ("HOME", "/home/me")]);
+
+        assert_eq!(
+            UnixLocator.locations(&env, "alacritty.toml"),
+            paths(&[
+                "/xdg/alacritty/alacritty.toml",
+                "/xdg/alacritty.toml",
+                "/home/me/.config/alacritty/alacritty.toml",
+                "/home/me/.alacritty.toml",
+                "/etc/alacritty/alacritty.toml",
+            ])
+        );
+    }
+
+    #[test]
+    fn unix_falls_back_to_home_config() {
+        // An empty XDG_CONFIG_HOME counts as unset
+        let env = env(&[("XDG_CONFIG_HOME", ""), ("HOME", "/home/me")]);
+
+        assert_eq!(
+            UnixLocator.locations(&env, "alacritty.toml"),
+            paths(&[
+                "/home/me/.config/alacritty/alacritty.toml",
+                "/home/me/.alacritty.toml",
+                "/etc/alacritty/alacritty.toml",
+            ])
+        );
+    }
+
+    #[test]
+    fn unix_lists_each_candidate_once() {
+        let env = env(&[
+            ("XDG_CONFIG_HOME", "/home/me/.config"),
+            ("HOME", "/home/me"),
+        ]);
+
+        assert_eq!(
+            UnixLocator.locations(&env, "alacritty.toml"),
+            paths(&[
+                "/home/me/.config/alacritty/alacritty.toml",
+                "/home/me/.config/alacritty.toml",
+                "/home/me/.alacritty.toml",
+                "/etc/alacritty/alacritty.toml",
+            ])
+        );
+    }
+
+    #[test]
+    fn toml_candidates_come_before_yaml_ones() {
+        let env = env(&[("HOME", "/home/me")]);
+
+        assert_eq!(
+            MacOsLocator.candidates(&env),
+            paths(&[
+                "/home/me/.config/alacritty/alacritty.toml",
+                "/home/me/.alacritty.toml",
+                "/etc/alacritty/alacritty.toml",
+                "/home/me/.config/alacritty/alacritty.yml",
+                "/home/me/.alacritty.yml",
+                "/etc/alacritty/alacritty.yml",
+                "/home/me/.config/alacritty/alacritty.yaml",
+                "/home/me/.alacritty.yaml",
+                "/etc/alacritty/alacritty.yaml",
+            ])
+        );
+    }
+
+    #[test]
+    fn windows_searches_appdata() {
+        let env = env(&[
+            ("APPDATA", "C:\\Users\\me\\AppData\\Roaming"),
+            ("HOME", "/home/me"),
+        ]);
+
+        assert_eq!(
+            WindowsLocator.candidates(&env),
+            paths(&[
+                "C:\\Users\\me\\AppData\\Roaming\\alacritty\\alacritty.toml",
+                "C:\\Users\\me\\AppData\\Roaming\\alacritty\\alacritty.yml",
+                "C:\\Users\\me\\AppData\\Roaming\\alacritty\\alacritty.yaml",
+            ])
+        );
+        assert!(WindowsLocator.candidates(&Environment::new()).is_empty());
+    }
+
+    #[test]
+    fn preferred_is_the_first_toml_location() {
+        let xdg = env(&[("XDG_CONFIG_HOME", "/xdg"), ("HOME", "/home/me")]);
+        let home = env(&[("HOME", "/home/me")]);
+        let windows = env(&[("APPDATA", "C:\\AppData")]);
+
+        assert_eq!(
+            UnixLocator.preferred(&xdg),
+            Some(PathBuf::from("/xdg/alacritty/alacritty.toml"))
+        );
+        assert_eq!(
+            MacOsLocator.preferred(&home),
+            Some(PathBuf::from("/home/me/.config/alacritty/alacritty.toml"))
+        );
+        assert_eq!(
+            WindowsLocator.preferred(&windows),
+            Some(PathBuf::from("C:\\AppData\\alacritty\\alacritty.toml"))
+        );
+        assert_eq!(WindowsLocator.preferred(&Environment::new()), None);
+    }
+
+    #[test]
+    fn preferred_is_never_system_wide() {
+        assert_eq!(UnixLocator.preferred(&Environment::new()), None);
+        assert_eq!(MacOsLocator.preferred(&Environment::new()), None);
+    }
+
+    #[test]
+    fn picks_the_locator_of_each_os() {
+        assert_eq!(for_os("linux").unwrap().platform(), "unix");
+        assert_eq!(for_os("freebsd").unwrap().platform(), "unix");
+        assert_eq!(for_os("macos").unwrap().platform(), "macos");
+        assert_eq!(for_os("windows").unwrap().platform(), "windows");
+        assert!(for_os("plan9").is_err());
+    }
+}
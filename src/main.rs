@@ -1,202 +1,79 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::{Color, Style};
-use ratatui::symbols;
-use ratatui::widgets::{BorderType, List, ListState, Tabs};
-use ratatui::{
-    layout::Rect,
-    style::Stylize,
-    widgets::{Block, Borders},
-    Frame,
-};
-use std::{fs, io};
-use toml_edit::DocumentMut;
+use std::path::Path;
+
+use alacritty::{AlacrittyConfig, AlacrittyTheme};
+use clap::Parser;
+use cli::Cli;
+use components::app::App;
+use config::Config;
+use keymap::Keymap;
 
 mod action;
 mod alacritty;
+mod cli;
 mod components;
+mod config;
+mod diff;
+mod export;
+mod fuzzy;
+mod gallery;
+mod hooks;
+mod import;
+mod keymap;
+mod locator;
+mod migrate;
+mod report;
+mod resolve;
+mod theme;
 mod tui;
 
-#[derive(Debug, Default)]
-pub struct App {
-    counter: u32,
-    exit: bool,
-    tabs: AppTab,
-    selected_tab: AppTab,
-}
-
-#[derive(Debug, Default)]
-pub enum AppTab {
-    #[default]
-    Themes,
-}
-
-impl App {
-    pub fn run(&mut self, terminal: &mut tui::Tui) -> io::Result<()> {
-        while !self.exit {
-            terminal.draw(|frame| self.render_frame(frame))?;
-            self.handle_events()?;
-        }
-
-        Ok(())
-    }
-
-    fn render_frame(&self, frame: &mut Frame) {
-        let area = Rect::new(0, 0, 8, 1);
-        let tabs = Tabs::new(vec!["Themes"])
-            .style(Style::new().bg(Color::Green).black())
-            .highlight_style(Style::new().yellow())
-            .select(2)
-            .divider(symbols::DOT);
-        frame.render_widget(tabs, area);
-
-        let canvas = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![Constraint::Length(1), Constraint::Percentage(100)])
-            .split(frame.size())[1];
-
-        let layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(vec![Constraint::Percentage(40), Constraint::Percentage(60)])
-            .split(canvas);
-
-        let left = layout[0];
-        let right = layout[1];
-
-        let theme_selection = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![Constraint::Length(3), Constraint::Percentage(100)])
-            .split(left);
-
-        let theme_selection_filter = theme_selection[0];
-        let theme_selection_list = theme_selection[1];
-
-        frame.render_widget(
-            Block::new()
-                .title("")
-                .title("🔍 (f) Filter")
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-            theme_selection_filter,
-        );
-
-        // This should be stored outside of the function in your application state.
-        let mut state = ListState::default();
-        state.select(Some(self.counter as usize));
-
-        let items = (1..10).map(|i| format!("Theme #{}", i.to_string()));
-        let list = List::new(items)
-            .block(
-                Block::default()
-                    .title("")
-                    .title("🎨 Select theme")
-                    .title_style(Style::new().white())
-                    .borders(Borders::ALL)
-                    .border_style(Style::new().green())
-                    .border_type(BorderType::Rounded),
-            )
-            .highlight_style(Style::new().bg(Color::Cyan).black())
-            .repeat_highlight_symbol(true);
-        frame.render_stateful_widget(list, theme_selection_list, &mut state);
-        frame.render_widget(
-            Block::new()
-            .title("")
-            .title("👁️  Preview")
-            .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-            right,
-        );
-    }
-
-    fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            // it's important to check that the event is a key press event as
-            // crossterm also emits key release and repeat events on Windows.
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event)
-            }
-            _ => {}
-        };
-
-        Ok(())
-    }
-
-    fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match key_event.code {
-            KeyCode::Char('q') => self.exit(),
-
-            KeyCode::Up => self.decrement_counter(),
-            KeyCode::Down => self.increment_counter(),
-
-            // Vim bindings
-            KeyCode::Char('k') => self.decrement_counter(),
-            KeyCode::Char('j') => self.increment_counter(),
-            _ => {}
-        }
-    }
-
-    fn exit(&mut self) {
-        self.exit = true;
-    }
-
-    fn increment_counter(&mut self) {
-        self.counter = self.counter.saturating_add(1) % 10;
-    }
-
-    fn decrement_counter(&mut self) {
-        self.counter = self.counter.saturating_sub(1);
-    }
-}
-
-fn try_main() -> Result<(), String> {
-    let platform = alacritty::detect_platform()?;
-    let config_file = alacritty::get_config_file_path(platform)?;
-    let config_file = fs::read_to_string(config_file).expect("Unable to read file");
-
-    let mut parsed = match config_file.parse::<DocumentMut>() {
-        Ok(parsed) => parsed,
-        Err(error) => return Err(format!("Failed to parse configuration file: {}", error)),
-    };
-
-    //  2.2 If it's not a toml return, only operate on toml
+fn try_main(
+    config_file: Option<&Path>,
+) -> Result<(Config, Keymap, AlacrittyConfig, Vec<AlacrittyTheme>), String> {
+    let config = Config::load()?;
+    let keymap = config.keymap().map_err(|error| {
+        let path = config
+            .path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        format!("Invalid keymap in {}:\n{}", path, error)
+    })?;
+
+    let alacritty_config = AlacrittyConfig::load(cli::config_file(config_file)?)?;
 
     // 3. Read the file, is it possible to keep it open?
     //    The idea is to have it continuosly open and write and save
     //    without closing it in a loop to make it a live preview
 
-    // 4. Display a nice looking UI that lists all the available themes,
-    //    lets you scroll through them, preview the colors and fuzzyfind some
-    // println!("Select a theme: {:?}", platform);
-
-    // 5. Add the theme to the config file
     let themes = alacritty::get_themes()?;
-    themes.iter().for_each(|theme| {
-        println!("{:?}", theme);
-    });
 
-    // 5. Return when escaping
-    let imports = parsed["import"].as_array_mut().expect("Not an array ahaha");
-
-    imports.push(format!(
-        "~/Projects/alacritty-skins/themes/{}.toml",
-        "gruvbox"
-    ));
-
-    // 6. Save the file
-    // fs::write("alacritty.toml", parsed.to_string()).expect("Unable to write file");
-
-    Ok(())
+    Ok((config, keymap, alacritty_config, themes))
 }
 
 fn main() {
-    if let Err(error) = try_main() {
-        eprintln!("Error: {}", error);
-        std::process::exit(1);
+    let cli = Cli::parse();
+    if let Some(command) = cli.command {
+        if let Err(error) = command.run(cli.config.as_deref()) {
+            eprintln!("Error: {}", error);
+            std::process::exit(1);
+        }
+        return;
     }
 
+    let (config, keymap, alacritty_config, themes) = match try_main(cli.config.as_deref()) {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            std::process::exit(1);
+        }
+    };
+
     let mut terminal = tui::init().unwrap();
-    let app_result = App::default().run(&mut terminal);
+    let app_result = App::new(config, keymap, alacritty_config, themes).run(&mut terminal);
     tui::restore().unwrap();
 
-    app_result.unwrap();
+    if let Err(error) = app_result {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
 }
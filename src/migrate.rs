@@ -0,0 +1,302 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_yaml::{Mapping, Value as Yaml};
+use toml_edit::{Array, ArrayOfTables, DocumentMut, InlineTable, Item, Table, Value};
+
+use crate::alacritty::{is_yaml, resolve_import, Change, GENERAL_KEYS};
+use crate::theme::Rgb;
+
+/// Keys Alacritty renamed or moved while the configuration was still YAML,
+/// as dotted paths from the old location to the new one
+const RENAMES: [(&str, &str); 5] = [
+    ("background_opacity", "window.opacity"),
+    ("key_bindings", "keyboard.bindings"),
+    ("mouse_bindings", "mouse.bindings"),
+    (
+        "draw_bold_text_with_bright_colors",
+        "colors.draw_bold_text_with_bright_colors",
+    ),
+    ("colors.search.bar", "colors.footer_bar"),
+];
+
+/// A YAML file converted to TOML, not written yet
+pub struct Migration {
+    pub source: PathBuf,
+    /// Creation of the TOML file
+    pub change: Change,
+    /// What the conversion changed besides the syntax
+    pub notes: Vec<String>,
+}
+
+/// Converts a YAML configuration file to TOML, along with the YAML files it
+/// imports. The imports are renamed to their `.toml` counterpart; imported
+/// files that already have one are left alone.
+pub fn migrate(path: &Path) -> Result<Vec<Migration>, String> {
+    let target = path.with_extension("toml");
+    if target.exists() {
+        return Err(format!(
+            "{} already exists, remove it first to migrate {}",
+            target.display(),
+            path.display()
+        ));
+    }
+
+    let mut migrations = Vec::new();
+    let mut visited = HashSet::new();
+    migrate_file(path, &mut visited, &mut migrations)?;
+
+    Ok(migrations)
+}
+
+fn migrate_file(
+    path: &Path,
+    visited: &mut HashSet<PathBuf>,
+    migrations: &mut Vec<Migration>,
+) -> Result<(), String> {
+    // Imports can form cycles, each file is only converted once
+    if !visited.insert(path.canonicalize().unwrap_or(path.to_path_buf())) {
+        return Ok(());
+    }
+
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+    let yaml: Yaml = serde_yaml::from_str(&contents)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    let mut root = match yaml {
+        Yaml::Mapping(root) => root,
+        Yaml::Null => Mapping::new(),
+        _ => return Err(format!("{} is not a YAML mapping", path.display())),
+    };
+
+    let mut notes = Vec::new();
+    for (from, to) in RENAMES {
+        if rename(&mut root, from, to) {
+            notes.push(format!("Moved `{}` to `{}`", from, to));
+        }
+    }
+
+    // Alacritty 0.14 reads these from [general], the top-level ones are
+    // deprecated
+    for key in GENERAL_KEYS {
+        if rename(&mut root, key, &format!("general.{}", key)) {
+            notes.push(format!("Moved `{}` under [general]", key));
+        }
+    }
+
+    let mut imported = Vec::new();
+    let imports = root
+        .get_mut("general")
+        .and_then(Yaml::as_mapping_mut)
+        .and_then(|general| general.get_mut("import"));
+    if let Some(Yaml::Sequence(imports)) = imports {
+        let config_dir = path.parent().unwrap_or(Path::new("."));
+        for import in imports.iter_mut() {
+            let Yaml::String(import) = import else {
+                continue;
+            };
+            let import_path = resolve_import(import, config_dir);
+            if !is_yaml(&import_path) {
+                continue;
+            }
+
+            let renamed = Path::new(import).with_extension("toml");
+            notes.push(format!(
+                "Imported `{}` instead of `{}`",
+                renamed.display(),
+                import
+            ));
+            *import = renamed.to_string_lossy().to_string();
+            if import_path.exists() && !import_path.with_extension("toml").exists() {
+                imported.push(import_path);
+            }
+        }
+    }
+
+    if let Some(Yaml::Mapping(colors)) = root.get_mut("colors") {
+        normalize_colors(colors);
+    }
+
+    // [general] comes first, as in the Alacritty documentation
+    let mut document = DocumentMut::new();
+    if let Some(general) = root.remove("general").and_then(to_item) {
+        document.insert("general", general);
+    }
+    for (key, value) in root {
+        if let Some(item) = to_item(value) {
+            document.insert(&key_name(&key), item);
+        }
+    }
+
+    migrations.push(Migration {
+        source: path.to_path_buf(),
+        change: Change::new(&path.with_extension("toml"), document.to_string()),
+        notes,
+    });
+
+    for import in imported {
+        migrate_file(&import, visited, migrations)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the value at the dotted path `from` to `to`, unless `to` is already
+/// set. Returns whether anything moved.
+fn rename(root: &mut Mapping, from: &str, to: &str) -> bool {
+    let (parents, key) = match from.rsplit_once('.') {
+        Some((parents, key)) => (Some(parents), key),
+        None => (None, from),
+    };
+    let parent = match parents {
+        Some(parents) => parents.split('.').try_fold(&mut *root, |mapping, name| {
+            mapping.get_mut(name).and_then(Yaml::as_mapping_mut)
+        }),
+        None => Some(&mut *root),
+    };
+    let Some(value) = parent.and_then(|parent| parent.remove(key)) else {
+        return false;
+    };
+
+    let (parents, key) = to.rsplit_once('.').unwrap_or(("", to));
+    let mut mapping = root;
+    for name in parents.split('.').filter(|name| !name.is_empty()) {
+        let entry = mapping
+            .entry(Yaml::from(name))
+            .or_insert_with(|| Yaml::Mapping(Mapping::new()));
+        let Some(child) = entry.as_mapping_mut() else {
+            return false;
+        };
+        mapping = child;
+    }
+    if mapping.contains_key(key) {
+        return false;
+    }
+    mapping.insert(Yaml::from(key), value);
+
+    true
+}
+
+/// Rewrites the `0xrrggbb` colours of the old schema as `#rrggbb`
+fn normalize_colors(mapping: &mut Mapping) {
+    for (_, value) in mapping.iter_mut() {
+        match value {
+            Yaml::String(color) => {
+                if let Some(rgb) = Rgb::parse(color) {
+                    *color = rgb.to_hex();
+                }
+            }
+            Yaml::Mapping(mapping) => normalize_colors(mapping),
+            Yaml::Sequence(values) => {
+                for value in values.iter_mut() {
+                    if let Yaml::Mapping(mapping) = value {
+                        normalize_colors(mapping);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn key_name(key: &Yaml) -> String {
+    match key {
+        Yaml::String(key) => key.clone(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+/// Converts a YAML value to a TOML item: mappings become tables and lists of
+/// mappings arrays of tables. TOML has no null, so null values are dropped.
+fn to_item(yaml: Yaml) -> Option<Item> {
+    match yaml {
+        Yaml::Mapping(mapping) => {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            for (key, value) in mapping {
+                if let Some(item) = to_item(value) {
+                    table.insert(&key_name(&key), item);
+                }
+            }
+            Some(Item::Table(table))
+        }
+        Yaml::Sequence(values)
+            if !values.is_empty() && values.iter().all(|value| value.is_mapping()) =>
+        {
+            let mut tables = ArrayOfTables::new();
+            for value in values {
+                if let Some(Item::Table(table)) = to_item(value) {
+                    tables.push(table);
+                }
+            }
+            Some(Item::ArrayOfTables(tables))
+        }
+        Yaml::Tagged(tagged) => to_item(tagged.value),
+        other => to_value(other).map(Item::Value),
+    }
+}
+
+fn to_value(yaml: Yaml) -> Option<Value> {
+    match yaml {
+        Yaml::Null => None,
+        Yaml::Bool(value) => Some(value.into()),
+        Yaml::Number(number) => match number.as_i64() {
+            Some(integer) => Some(integer.into()),
+            None => number.as_f64().map(Value::from),
+        },
+        Yaml::String(value) => Some(value.into()),
+        Yaml::Sequence(values) => Some(Value::Array(
+            values.into_iter().filter_map(to_value).collect::<Array>(),
+        )),
+        Yaml::Mapping(mapping) => {
+            let mut table = InlineTable::new();
+            for (key, value) in mapping {
+                if let Some(value) = to_value(value) {
+                    table.insert(key_name(&key), value);
+                }
+            }
+            Some(Value::InlineTable(table))
+        }
+        Yaml::Tagged(tagged) => to_value(tagged.value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn moves_import_under_general() {
+        let dir = env::temp_dir().join(format!("alacritty-skins-migrate-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("alacritty.yml");
+        fs::write(
+            &config,
+            "font:\n  size: 11\nimport:\n  - theme.toml\nlive_config_reload: false\n",
+        )
+        .unwrap();
+
+        let migrations = migrate(&config).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            migrations[0].change.after,
+            "[general]\nimport = [\"theme.toml\"]\nlive_config_reload = false\n\n[font]\nsize = 11\n"
+        );
+        assert_eq!(
+            migrations[0].notes,
+            vec![
+                "Moved `import` under [general]",
+                "Moved `live_config_reload` under [general]",
+            ]
+        );
+    }
+}
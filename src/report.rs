@@ -0,0 +1,305 @@
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use crate::alacritty::{self, AlacrittyTheme};
+use crate::theme::Palette;
+
+/// Characters separating the words of a theme name
+const SEPARATORS: [char; 4] = ['-', '_', '.', ' '];
+
+/// How the report is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// A Markdown table
+    Markdown,
+    /// Comma separated values, with a header line
+    Csv,
+    /// An array of objects, one per theme
+    Json,
+}
+
+/// A line of the report
+#[derive(Debug, Clone, PartialEq)]
+struct Row {
+    name: String,
+    family: String,
+    variant: &'static str,
+    background: Option<String>,
+    foreground: Option<String>,
+    min_ansi_contrast: Option<f64>,
+    source: String,
+}
+
+/// The colours of every theme of the library, one row per theme
+#[derive(Debug, Default)]
+pub struct Report {
+    rows: Vec<Row>,
+    /// Themes left out because they could not be read
+    pub warnings: Vec<String>,
+}
+
+impl Report {
+    pub fn new(themes: &[AlacrittyTheme]) -> Self {
+        let mut report = Self::default();
+        for theme in themes {
+            let palette = match Palette::load(&theme.path) {
+                Ok(palette) => palette,
+                Err(error) => {
+                    report.warnings.push(error);
+                    continue;
+                }
+            };
+
+            report.rows.push(Row {
+                name: theme.name.clone(),
+                family: family(&theme.name, themes),
+                variant: palette.variant(),
+                background: palette.background.map(|color| color.to_hex()),
+                foreground: palette.foreground.map(|color| color.to_hex()),
+                min_ansi_contrast: palette.min_ansi_contrast(),
+                source: theme
+                    .path
+                    .parent()
+                    .map(alacritty::contract_home)
+                    .unwrap_or_default(),
+            });
+        }
+
+        report
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Markdown => self.markdown(),
+            Format::Csv => self.csv(),
+            Format::Json => self.json(),
+        }
+    }
+
+    fn markdown(&self) -> String {
+        let mut lines = vec![
+            "| Name | Family | Variant | Background | Foreground | Min ANSI contrast | Source |"
+                .to_string(),
+            "| --- | --- | --- | --- | --- | ---: | --- |".to_string(),
+        ];
+        for row in &self.rows {
+            let cells = row.cells().map(|cell| cell.replace('|', "\\|"));
+            lines.push(format!("| {} |", cells.join(" | ")));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    fn csv(&self) -> String {
+        let mut lines =
+            vec!["name,family,variant,background,foreground,min_ansi_contrast,source".to_string()];
+        for row in &self.rows {
+            let cells = row
+                .cells()
+                .map(|cell| match cell.contains([',', '"', '\n']) {
+                    true => format!("\"{}\"", cell.replace('"', "\"\"")),
+                    false => cell,
+                });
+            lines.push(cells.join(","));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    fn json(&self) -> String {
+        let rows: Vec<Value> = self
+            .rows
+            .iter()
+            .map(|row| {
+                // Two decimals, as in the other formats
+                let contrast = row
+                    .min_ansi_contrast
+                    .map(|contrast| (contrast * 100.0).round() / 100.0);
+                json!({
+                    "name": row.name,
+                    "family": row.family,
+                    "variant": row.variant,
+                    "background": row.background,
+                    "foreground": row.foreground,
+                    "min_ansi_contrast": contrast,
+                    "source": row.source,
+                })
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&rows).unwrap_or_default() + "\n"
+    }
+}
+
+impl Row {
+    /// The columns of the row as text, empty for the colours left out
+    fn cells(&self) -> [String; 7] {
+        [
+            self.name.clone(),
+            self.family.clone(),
+            self.variant.to_string(),
+            self.background.clone().unwrap_or_default(),
+            self.foreground.clone().unwrap_or_default(),
+            self.min_ansi_contrast
+                .map(|contrast| format!("{:.2}", contrast))
+                .unwrap_or_default(),
+            self.source.clone(),
+        ]
+    }
+}
+
+/// The first word of the name when another theme starts with it too, as in
+/// `Gruvbox-Dark` and `Gruvbox-Light`, or else the whole name
+fn family(name: &str, themes: &[AlacrittyTheme]) -> String {
+    let Some((prefix, _)) = name.split_once(SEPARATORS) else {
+        return name.to_string();
+    };
+
+    let shared = themes.iter().any(|theme| {
+        theme.name != name
+            && theme
+                .name
+                .split(SEPARATORS)
+                .next()
+                .is_some_and(|first| first.eq_ignore_ascii_case(prefix))
+    });
+    match shared {
+        true => prefix.to_string(),
+        false => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    fn row(name: &str, contrast: Option<f64>) -> Row {
+        Row {
+            name: name.to_string(),
+            family: "Family".to_string(),
+            variant: "dark",
+            background: Some("#000000".to_string()),
+            foreground: None,
+            min_ansi_contrast: contrast,
+            source: "~/themes".to_string(),
+        }
+    }
+
+    fn report() -> Report {
+        Report {
+            rows: vec![
+                row("Plain", Some(4.567)),
+                row("Pipe | \"quoted\", comma", None),
+            ],
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn renders_markdown() {
+        assert_eq!(
+            report().render(Format::Markdown),
+            "| Name | Family | Variant | Background | Foreground | Min ANSI contrast | Source |\n\
+             | --- | --- | --- | --- | --- | ---: | --- |\n\
+             | Plain | Family | dark | #000000 |  | 4.57 | ~/themes |\n\
+             | Pipe \\| \"quoted\", comma | Family | dark | #000000 |  |  | ~/themes |\n"
+        );
+    }
+
+    #[test]
+    fn renders_csv() {
+        assert_eq!(
+            report().render(Format::Csv),
+            "name,family,variant,background,foreground,min_ansi_contrast,source\n\
+             Plain,Family,dark,#000000,,4.57,~/themes\n\
+             \"Pipe | \"\"quoted\"\", comma\",Family,dark,#000000,,,~/themes\n"
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let json: Value = serde_json::from_str(&report().render(Format::Json)).unwrap();
+
+        assert_eq!(
+            json,
+            json!([
+                {
+                    "name": "Plain",
+                    "family": "Family",
+                    "variant": "dark",
+                    "background": "#000000",
+                    "foreground": null,
+                    "min_ansi_contrast": 4.57,
+                    "source": "~/themes",
+                },
+                {
+                    "name": "Pipe | \"quoted\", comma",
+                    "family": "Family",
+                    "variant": "dark",
+                    "background": "#000000",
+                    "foreground": null,
+                    "min_ansi_contrast": null,
+                    "source": "~/themes",
+                },
+            ])
+        );
+        // Keys keep the column order
+        let keys: Vec<&String> = json[0].as_object().unwrap().keys().collect();
+        assert_eq!(
+            keys,
+            [
+                "name",
+                "family",
+                "variant",
+                "background",
+                "foreground",
+                "min_ansi_contrast",
+                "source"
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_the_themes() {
+        let dir = env::temp_dir().join(format!("alacritty-skins-report-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let themes: Vec<AlacrittyTheme> = [
+            ("Gruvbox-Dark", "#282828"),
+            ("Gruvbox-Light", "#fbf1c7"),
+            ("Nord", "#2e3440"),
+        ]
+        .iter()
+        .map(|(name, background)| {
+            let path = dir.join(format!("{}.toml", name));
+            fs::write(
+                &path,
+                format!("[colors.primary]\nbackground = \"{}\"\n", background),
+            )
+            .unwrap();
+            AlacrittyTheme::new(path)
+        })
+        .chain([AlacrittyTheme::new(dir.join("Missing.toml"))])
+        .collect();
+
+        let report = Report::new(&themes);
+        let _ = fs::remove_dir_all(&dir);
+
+        let rows: Vec<(&str, &str, &str)> = report
+            .rows
+            .iter()
+            .map(|row| (row.name.as_str(), row.family.as_str(), row.variant))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("Gruvbox-Dark", "Gruvbox", "dark"),
+                ("Gruvbox-Light", "Gruvbox", "light"),
+                ("Nord", "Nord", "dark"),
+            ]
+        );
+        assert_eq!(report.rows[0].source, alacritty::contract_home(&dir));
+        assert_eq!(report.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml_edit::DocumentMut;
+
+use crate::alacritty::{imports, resolve_import};
+use crate::theme::{Palette, Rgb};
+
+/// How deep Alacritty follows imports of imports
+const IMPORT_DEPTH_LIMIT: usize = 5;
+
+/// A colour of the effective palette and the file it is set by
+#[derive(Debug, Clone)]
+pub struct ColorSource {
+    /// Alacritty key of the colour, such as `primary.background`
+    pub name: String,
+    pub color: Rgb,
+    pub file: PathBuf,
+}
+
+/// The colours Alacritty ends up using for a configuration file. Imports are
+/// loaded in order, each one after its own imports, and the file importing
+/// them comes last, so later files override earlier ones.
+#[derive(Debug, Default)]
+pub struct EffectiveColors {
+    pub palette: Palette,
+    /// Every colour of the palette with the file it comes from
+    pub sources: Vec<ColorSource>,
+    /// Imports that could not be loaded, which Alacritty skips as well
+    pub warnings: Vec<String>,
+    files: HashMap<String, PathBuf>,
+}
+
+impl EffectiveColors {
+    pub fn resolve(config: &Path) -> Result<Self, String> {
+        let mut colors = Self::default();
+        colors.load(config, &mut Vec::new())?;
+
+        colors.sources = colors
+            .palette
+            .entries()
+            .into_iter()
+            .filter_map(|(name, color)| {
+                let file = colors.files.get(&name)?.clone();
+                Some(ColorSource { name, color, file })
+            })
+            .collect();
+
+        Ok(colors)
+    }
+
+    /// Loads a file after its imports. `chain` holds the files importing it,
+    /// down from the configuration file.
+    fn load(&mut self, path: &Path, chain: &mut Vec<PathBuf>) -> Result<(), String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+        let document = contents
+            .parse::<DocumentMut>()
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+        chain.push(canonical(path));
+        let config_dir = path.parent().unwrap_or(Path::new("."));
+        for import in imports(&document) {
+            let import = resolve_import(&import, config_dir);
+            if chain.contains(&canonical(&import)) {
+                let cycle: Vec<String> = chain
+                    .iter()
+                    .chain([&import])
+                    .map(|file| file.display().to_string())
+                    .collect();
+                self.warnings
+                    .push(format!("Import cycle: {}", cycle.join(" -> ")));
+            } else if chain.len() > IMPORT_DEPTH_LIMIT {
+                self.warnings.push(format!(
+                    "Skipped {}, imports are only followed {} levels deep",
+                    import.display(),
+                    IMPORT_DEPTH_LIMIT
+                ));
+            } else if !import.exists() {
+                self.warnings
+                    .push(format!("Unable to find import {}", import.display()));
+            } else if let Err(error) = self.load(&import, chain) {
+                self.warnings.push(error);
+            }
+        }
+        chain.pop();
+
+        let palette = Palette::from_document(&document);
+        for (name, _) in palette.entries() {
+            self.files.insert(name, path.to_path_buf());
+        }
+        self.palette.merge(&palette);
+
+        Ok(())
+    }
+
+    /// Colours set by the theme that another file overrides, so that they do
+    /// not show in Alacritty
+    pub fn shadowing(&self, theme: &Path) -> Result<Vec<&ColorSource>, String> {
+        let theme_colors = Palette::load(theme)?.entries();
+        let theme = canonical(theme);
+
+        Ok(self
+            .sources
+            .iter()
+            .filter(|source| theme_colors.iter().any(|(name, _)| *name == source.name))
+            .filter(|source| canonical(&source.file) != theme)
+            .collect())
+    }
+}
+
+/// Summarizes which files override the colours of a theme, as in
+/// `primary.background, primary.foreground set by alacritty.toml`
+pub fn describe_shadowing(shadowing: &[&ColorSource]) -> String {
+    let mut files: Vec<(&Path, Vec<&str>)> = Vec::new();
+    for source in shadowing {
+        match files.iter_mut().find(|(file, _)| *file == source.file) {
+            Some((_, names)) => names.push(&source.name),
+            None => files.push((&source.file, vec![&source.name])),
+        }
+    }
+
+    files
+        .iter()
+        .map(|(file, names)| format!("{} set by {}", names.join(", "), file.display()))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    /// A scratch directory holding the given files, removed by the caller
+    fn files(test: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "alacritty-skins-resolve-{}-{}",
+            test,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, contents) in files {
+            fs::write(dir.join(name), contents).unwrap();
+        }
+
+        dir
+    }
+
+    #[test]
+    fn reports_import_cycles() {
+        let dir = files(
+            "cycle",
+            &[
+                (
+                    "alacritty.toml",
+                    "[general]\nimport = [\"a.toml\"]\n[colors.primary]\nforeground = \"#ffffff\"\n",
+                ),
+                (
+                    "a.toml",
+                    "import = [\"alacritty.toml\"]\n[colors.primary]\nbackground = \"#000000\"\n",
+                ),
+            ],
+        );
+        let colors = EffectiveColors::resolve(&dir.join("alacritty.toml")).unwrap();
+        let root = canonical(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            colors.warnings,
+            vec![format!(
+                "Import cycle: {} -> {} -> {}",
+                root.join("alacritty.toml").display(),
+                root.join("a.toml").display(),
+                dir.join("alacritty.toml").display(),
+            )]
+        );
+        assert_eq!(colors.palette.background, Rgb::parse("#000000"));
+        assert_eq!(colors.palette.foreground, Rgb::parse("#ffffff"));
+    }
+
+    #[test]
+    fn follows_imports_five_levels_deep() {
+        // alacritty.toml imports 1.toml, which imports 2.toml, down to 6.toml.
+        // Each file sets the ANSI colour of its level.
+        let mut contents = vec![(
+            "alacritty.toml".to_string(),
+            "[general]\nimport = [\"1.toml\"]\n".to_string(),
+        )];
+        for level in 1..=6 {
+            contents.push((
+                format!("{}.toml", level),
+                format!(
+                    "import = [\"{}.toml\"]\n[colors.normal]\n{} = \"#00000{}\"\n",
+                    level + 1,
+                    crate::theme::ANSI_NAMES[level],
+                    level
+                ),
+            ));
+        }
+        let contents: Vec<(&str, &str)> = contents
+            .iter()
+            .map(|(name, contents)| (name.as_str(), contents.as_str()))
+            .collect();
+        let dir = files("depth", &contents);
+        let colors = EffectiveColors::resolve(&dir.join("alacritty.toml")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        for level in 1..=5 {
+            assert_eq!(
+                colors.palette.normal[level],
+                Rgb::parse(&format!("#00000{}", level))
+            );
+        }
+        assert_eq!(colors.palette.normal[6], None);
+        assert_eq!(
+            colors.warnings,
+            vec![format!(
+                "Skipped {}, imports are only followed 5 levels deep",
+                dir.join("6.toml").display()
+            )]
+        );
+    }
+
+    #[test]
+    fn reports_missing_imports() {
+        let dir = files(
+            "missing",
+            &[("alacritty.toml", "[general]\nimport = [\"gone.toml\"]\n")],
+        );
+        let colors = EffectiveColors::resolve(&dir.join("alacritty.toml")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            colors.warnings,
+            vec![format!(
+                "Unable to find import {}",
+                dir.join("gone.toml").display()
+            )]
+        );
+    }
+}
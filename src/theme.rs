@@ -0,0 +1,244 @@
+use std::{fs, path::Path};
+
+use toml_edit::{value, DocumentMut, Item, Table};
+
+/// Names of the eight ANSI colours, in palette order
+pub const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Text of the mock terminal drawn for a theme, one slice of spans per line.
+/// Each span is drawn in the ANSI colour of its index, or in the foreground.
+pub const SAMPLE: [&[(&str, Option<usize>)]; 5] = [
+    &[
+        (" ", None),
+        ("user@host", Some(2)),
+        (":", None),
+        ("~/alacritty-skins", Some(4)),
+        ("$ git status", None),
+    ],
+    &[(" On branch ", None), ("main", Some(5))],
+    &[("   ", None), ("modified:   src/main.rs", Some(1))],
+    &[("   ", None), ("new file:   src/theme.rs", Some(2))],
+    &[
+        (" ", None),
+        ("warning:", Some(3)),
+        (" 2 files need ", None),
+        ("review", Some(6)),
+    ],
+];
+
+/// A 24-bit colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Parses the colour notations accepted by Alacritty: `#rrggbb`,
+    /// `0xrrggbb` and the short `#rgb`
+    pub fn parse(value: &str) -> Option<Self> {
+        let hex = value
+            .trim()
+            .strip_prefix('#')
+            .or_else(|| value.trim().strip_prefix("0x"))?;
+        let channel = |range: std::ops::Range<usize>| u8::from_str_radix(hex.get(range)?, 16).ok();
+
+        match hex.len() {
+            6 => Some(Self::new(channel(0..2)?, channel(2..4)?, channel(4..6)?)),
+            3 => {
+                let (r, g, b) = (channel(0..1)?, channel(1..2)?, channel(2..3)?);
+                Some(Self::new(r * 17, g * 17, b * 17))
+            }
+            _ => None,
+        }
+    }
+
+    /// Formats the colour as `#rrggbb`
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Relative luminance as defined by WCAG, from 0 for black to 1 for white
+    pub fn luminance(self) -> f64 {
+        let linear = |channel: u8| {
+            let channel = channel as f64 / 255.0;
+            match channel <= 0.04045 {
+                true => channel / 12.92,
+                false => ((channel + 0.055) / 1.055).powf(2.4),
+            }
+        };
+
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// WCAG contrast ratio between two colours, from 1 for identical
+    /// luminances to 21 for black on white
+    pub fn contrast(self, other: Rgb) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
+
+/// The colours of an Alacritty theme that alacritty-skins understands. Colours
+/// the theme leaves out, or sets to a cell reference such as
+/// `CellForeground`, are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Palette {
+    pub background: Option<Rgb>,
+    pub foreground: Option<Rgb>,
+    pub normal: [Option<Rgb>; 8],
+    pub bright: [Option<Rgb>; 8],
+    pub cursor: Option<Rgb>,
+    pub cursor_text: Option<Rgb>,
+    pub selection_background: Option<Rgb>,
+    pub selection_text: Option<Rgb>,
+}
+
+impl Palette {
+    /// Reads the palette of an Alacritty theme or configuration file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+        let document = contents
+            .parse::<DocumentMut>()
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+        Ok(Self::from_document(&document))
+    }
+
+    /// Reads the palette from the `[colors]` tables of a parsed document
+    pub fn from_document(document: &DocumentMut) -> Self {
+        let colors = document.get("colors");
+        let color = |section: &str, key: &str| {
+            colors
+                .and_then(|colors| colors.get(section))
+                .and_then(|section| section.get(key))
+                .and_then(Item::as_str)
+                .and_then(Rgb::parse)
+        };
+
+        Self {
+            background: color("primary", "background"),
+            foreground: color("primary", "foreground"),
+            normal: ANSI_NAMES.map(|name| color("normal", name)),
+            bright: ANSI_NAMES.map(|name| color("bright", name)),
+            cursor: color("cursor", "cursor"),
+            cursor_text: color("cursor", "text"),
+            selection_background: color("selection", "background"),
+            selection_text: color("selection", "text"),
+        }
+    }
+
+    /// Overrides the colours of the palette with the ones `other` sets
+    pub fn merge(&mut self, other: &Palette) {
+        let merge = |color: &mut Option<Rgb>, other: Option<Rgb>| *color = other.or(*color);
+
+        merge(&mut self.background, other.background);
+        merge(&mut self.foreground, other.foreground);
+        for (color, other) in self.normal.iter_mut().zip(other.normal) {
+            merge(color, other);
+        }
+        for (color, other) in self.bright.iter_mut().zip(other.bright) {
+            merge(color, other);
+        }
+        merge(&mut self.cursor, other.cursor);
+        merge(&mut self.cursor_text, other.cursor_text);
+        merge(&mut self.selection_background, other.selection_background);
+        merge(&mut self.selection_text, other.selection_text);
+    }
+
+    /// Every colour set in the palette, named after its Alacritty key such as
+    /// `primary.background` or `normal.red`
+    pub fn entries(&self) -> Vec<(String, Rgb)> {
+        let mut entries = vec![
+            ("primary.background".to_string(), self.background),
+            ("primary.foreground".to_string(), self.foreground),
+        ];
+        for (section, colors) in [("normal", &self.normal), ("bright", &self.bright)] {
+            for (name, color) in ANSI_NAMES.iter().zip(colors) {
+                entries.push((format!("{}.{}", section, name), *color));
+            }
+        }
+        entries.extend([
+            ("cursor.cursor".to_string(), self.cursor),
+            ("cursor.text".to_string(), self.cursor_text),
+            (
+                "selection.background".to_string(),
+                self.selection_background,
+            ),
+            ("selection.text".to_string(), self.selection_text),
+        ]);
+
+        entries
+            .into_iter()
+            .filter_map(|(name, color)| Some((name, color?)))
+            .collect()
+    }
+
+    /// Whether the background is light, that is when black text on it has
+    /// more contrast than white text. A palette without background is dark,
+    /// like the Alacritty default.
+    pub fn is_light(&self) -> bool {
+        self.background
+            .is_some_and(|background| background.luminance() > 0.179)
+    }
+
+    /// Contrast between the foreground and the background
+    pub fn contrast(&self) -> Option<f64> {
+        Some(self.foreground?.contrast(self.background?))
+    }
+
+    /// Lowest contrast with the background among the ANSI colours text is
+    /// drawn in, that is all of them but the two meant to blend into the
+    /// background: black and bright black on a dark theme, white and bright
+    /// white on a light one
+    pub fn min_ansi_contrast(&self) -> Option<f64> {
+        let background = self.background?;
+        let text = match self.is_light() {
+            true => 0..7,
+            false => 1..8,
+        };
+        self.normal[text.clone()]
+            .iter()
+            .chain(&self.bright[text])
+            .flatten()
+            .map(|color| color.contrast(background))
+            .min_by(f64::total_cmp)
+    }
+
+    /// `light` or `dark`, after `is_light`
+    pub fn variant(&self) -> &'static str {
+        match self.is_light() {
+            true => "light",
+            false => "dark",
+        }
+    }
+
+    /// Writes the palette as an Alacritty theme, one `[colors.*]` table per
+    /// section the palette sets
+    pub fn to_toml(&self) -> String {
+        let mut document = DocumentMut::new();
+        let mut colors = Table::new();
+        colors.set_implicit(true);
+
+        for (name, color) in self.entries() {
+            let (section, key) = name.split_once('.').unwrap_or(("primary", &name));
+            let table = colors
+                .entry(section)
+                .or_insert(Item::Table(Table::new()))
+                .as_table_mut()
+                .expect("colour sections are tables");
+            table.insert(key, value(color.to_hex()));
+        }
+        document.insert("colors", Item::Table(colors));
+
+        document.to_string()
+    }
+}